@@ -0,0 +1,15 @@
+use std::io::Read;
+use std::path::Path;
+
+/// Takes the input from the argument if given, else from `file`, else from stdin.
+pub fn read(input: Option<String>, file: Option<&Path>) -> Result<String, String> {
+    if let Some(input) = input {
+        return Ok(input);
+    }
+    if let Some(file) = file {
+        return std::fs::read_to_string(file).map_err(|err| format!("cannot read {}: {err}", file.display()));
+    }
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input).map_err(|err| format!("cannot read stdin: {err}"))?;
+    Ok(input)
+}
@@ -0,0 +1,104 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+mod input;
+mod output;
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+use algoviz_core::input::{parse_grid, parse_numbers};
+use algoviz_core::pathfinding::{self, PathAlgorithm};
+use algoviz_core::sorting::{self, AlgorithmType};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use strum::IntoEnumIterator;
+
+/// Runs the project's algorithms from the shell and prints their results and traces.
+#[derive(Parser, Debug)]
+#[command(name = "algoviz", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Sort a list of integers, e.g. `algoviz sort quick --input 5,3,8,1`.
+    Sort {
+        /// Sorting algorithm; see `algoviz list`.
+        algorithm: AlgorithmType,
+        /// Integers separated by commas or whitespace.
+        #[arg(long, conflicts_with = "file")]
+        input: Option<String>,
+        #[command(flatten)]
+        options: RunOptions,
+    },
+    /// Find a path through a grid given as JSON or as a text map.
+    ///
+    /// JSON takes the same body as `POST /path/<algorithm>`. A text map has one
+    /// line per row: `.` is open, `#` a wall, `S` the start, `E` the end and
+    /// `1`-`9` the cost of entering the cell.
+    Path {
+        /// Pathfinding algorithm; see `algoviz list`.
+        algorithm: PathAlgorithm,
+        /// The grid itself instead of a file or stdin.
+        #[arg(long, conflicts_with = "file")]
+        input: Option<String>,
+        #[command(flatten)]
+        options: RunOptions,
+    },
+    /// List the algorithms the other commands accept.
+    List,
+}
+
+#[derive(Args, Debug)]
+struct RunOptions {
+    /// Read the input from this file; without it or `--input`, stdin is read.
+    #[arg(long)]
+    file: Option<PathBuf>,
+    #[arg(long, value_enum, default_value_t = Format::Json)]
+    format: Format,
+    /// Print only the result and metrics, without the recorded trace.
+    #[arg(long)]
+    no_trace: bool,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Json,
+    /// One table: the trace, or a single summary row with `--no-trace`.
+    Csv,
+    Text,
+}
+
+fn run(command: Command) -> Result<String, String> {
+    match command {
+        Command::Sort { algorithm, input, options } => {
+            let numbers = parse_numbers(&input::read(input, options.file.as_deref())?)?;
+            let sorted = sorting::sort(algorithm, numbers.clone(), Vec::new());
+            Ok(output::sort(options.format, algorithm, &numbers, &sorted, !options.no_trace))
+        }
+        Command::Path { algorithm, input, options } => {
+            let spec = parse_grid(&input::read(input, options.file.as_deref())?)?;
+            let (grid, start, end) = spec.into_grid()?;
+            let result = pathfinding::search(algorithm, &grid, start, end);
+            Ok(output::path(options.format, algorithm, &grid, start, end, &result, !options.no_trace))
+        }
+        Command::List => {
+            let sorts = AlgorithmType::iter().map(|algorithm| format!("sort {:<10} {}\n", algorithm.slug(), algorithm.info().name));
+            let paths = PathAlgorithm::iter().map(|algorithm| format!("path {}\n", algorithm.slug()));
+            Ok(sorts.chain(paths).collect())
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse().command) {
+        Ok(printed) => {
+            print!("{printed}");
+            ExitCode::SUCCESS
+        }
+        Err(reason) => {
+            eprintln!("error: {reason}");
+            ExitCode::FAILURE
+        }
+    }
+}
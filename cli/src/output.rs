@@ -0,0 +1,192 @@
+use std::fmt::Write;
+use algoviz_core::pathfinding::{Cell, Grid, PathAlgorithm, PathResult};
+use algoviz_core::sorting::{AlgorithmType, Sorted};
+use algoviz_core::trace::{Metrics, Operation, Phase};
+use serde_json::json;
+
+use crate::Format;
+
+pub fn sort(format: Format, algorithm: AlgorithmType, input: &[i64], sorted: &Sorted<Vec<Operation>>, with_trace: bool) -> String {
+    match format {
+        Format::Json => {
+            let mut body = json!({
+                "algorithm": algorithm,
+                "input": input,
+                "result": sorted.result,
+                "metrics": sorted.metrics,
+            });
+            if with_trace {
+                body["operations"] = json!(sorted.operations);
+            }
+            format!("{body}\n")
+        }
+        Format::Csv if with_trace => {
+            let mut csv = String::from("step,op,i,j,index,value,start,end,phase\n");
+            for (step, operation) in sorted.operations.iter().enumerate() {
+                let [i, j, index, value, start, end, phase] = operation_fields(operation);
+                let _ = writeln!(csv, "{step},{},{i},{j},{index},{value},{start},{end},{phase}", operation_name(operation));
+            }
+            csv
+        }
+        Format::Csv => {
+            let Metrics { comparisons, swaps, reads, writes, auxiliary_memory } = sorted.metrics;
+            format!(
+                "result,comparisons,swaps,reads,writes,auxiliary_memory\n{},{comparisons},{swaps},{reads},{writes},{auxiliary_memory}\n",
+                join(&sorted.result),
+            )
+        }
+        Format::Text => {
+            let Metrics { comparisons, swaps, reads, writes, auxiliary_memory } = sorted.metrics;
+            let mut text = format!(
+                "algorithm: {}\ninput: {}\nresult: {}\ncomparisons: {comparisons}, swaps: {swaps}, reads: {reads}, writes: {writes}, auxiliary memory: {auxiliary_memory}\n",
+                algorithm.info().name,
+                join(input),
+                join(&sorted.result),
+            );
+            if with_trace {
+                let _ = writeln!(text, "trace: {} operations", sorted.operations.len());
+                for (step, operation) in sorted.operations.iter().enumerate() {
+                    let _ = writeln!(text, "{step:>6}  {}", describe(operation));
+                }
+            }
+            text
+        }
+    }
+}
+
+pub fn path(format: Format, algorithm: PathAlgorithm, grid: &Grid, start: Cell, end: Cell, result: &PathResult, with_trace: bool) -> String {
+    match format {
+        Format::Json => {
+            let mut body = json!({
+                "algorithm": algorithm,
+                "path": result.path,
+                "cost": result.cost,
+                "nodes_expanded": result.nodes_expanded,
+            });
+            if with_trace {
+                body["visited"] = json!(result.visited);
+            }
+            format!("{body}\n")
+        }
+        Format::Csv if with_trace => {
+            let mut csv = String::from("kind,step,row,col\n");
+            for (kind, cells) in [("visited", &result.visited), ("path", &result.path)] {
+                for (step, (row, col)) in cells.iter().enumerate() {
+                    let _ = writeln!(csv, "{kind},{step},{row},{col}");
+                }
+            }
+            csv
+        }
+        Format::Csv => format!(
+            "cost,nodes_expanded,path\n{},{},{}\n",
+            result.cost.map(|cost| cost.to_string()).unwrap_or_default(),
+            result.nodes_expanded,
+            join_cells(&result.path),
+        ),
+        Format::Text => {
+            let cost = result.cost.map_or_else(|| "unreachable".to_string(), |cost| cost.to_string());
+            let mut text = format!("algorithm: {algorithm}\ncost: {cost}\nnodes expanded: {}\n", result.nodes_expanded);
+            if with_trace {
+                let _ = writeln!(text, "visited: {}", join_cells(&result.visited));
+            }
+            let _ = writeln!(text, "path: {}", join_cells(&result.path));
+            text.push_str(&render_map(grid, start, end, result, with_trace));
+            text
+        }
+    }
+}
+
+/// The grid as a text map in the input syntax, with the path drawn as `*`,
+/// expanded cells as `o` when the trace is shown and weights above 9 as `+`.
+fn render_map(grid: &Grid, start: Cell, end: Cell, result: &PathResult, with_trace: bool) -> String {
+    let mut symbols: Vec<Vec<char>> = (0..grid.rows())
+        .map(|row| {
+            (0..grid.cols())
+                .map(|col| match grid.weight((row, col)) {
+                    _ if grid.is_wall((row, col)) => '#',
+                    1 => '.',
+                    weight @ 2..=9 => char::from_digit(u32::try_from(weight).unwrap_or(9), 10).unwrap_or('9'),
+                    _ => '+',
+                })
+                .collect()
+        })
+        .collect();
+    if with_trace {
+        for &(row, col) in &result.visited {
+            symbols[row][col] = 'o';
+        }
+    }
+    for &(row, col) in &result.path {
+        symbols[row][col] = '*';
+    }
+    symbols[start.0][start.1] = 'S';
+    symbols[end.0][end.1] = 'E';
+    symbols.into_iter().map(|row| row.into_iter().chain(['\n']).collect::<String>()).collect()
+}
+
+/// The tag the operation is serialized under.
+fn operation_name(operation: &Operation) -> &'static str {
+    match operation {
+        Operation::Compare { .. } => "compare",
+        Operation::Swap { .. } => "swap",
+        Operation::Write { .. } => "write",
+        Operation::MarkSorted { .. } => "mark_sorted",
+        Operation::EnterRange { .. } => "enter_range",
+        Operation::LeaveRange { .. } => "leave_range",
+        Operation::Phase { .. } => "phase",
+        Operation::HeapBoundary { .. } => "heap_boundary",
+    }
+}
+
+fn phase_name(phase: Phase) -> &'static str {
+    match phase {
+        Phase::BuildHeap => "build_heap",
+        Phase::ExtractMax => "extract_max",
+    }
+}
+
+/// The CSV columns `i, j, index, value, start, end, phase`, empty where the
+/// operation has no such field.
+fn operation_fields(operation: &Operation) -> [String; 7] {
+    let mut fields: [String; 7] = Default::default();
+    match *operation {
+        Operation::Compare { i, j } | Operation::Swap { i, j } => {
+            fields[0] = i.to_string();
+            fields[1] = j.to_string();
+        }
+        Operation::Write { index, value } => {
+            fields[2] = index.to_string();
+            fields[3] = value.to_string();
+        }
+        Operation::MarkSorted { index } => fields[2] = index.to_string(),
+        Operation::EnterRange { start, end } | Operation::LeaveRange { start, end } => {
+            fields[4] = start.to_string();
+            fields[5] = end.to_string();
+        }
+        Operation::Phase { phase } => fields[6] = phase_name(phase).to_string(),
+        Operation::HeapBoundary { end } => fields[5] = end.to_string(),
+    }
+    fields
+}
+
+fn describe(operation: &Operation) -> String {
+    match *operation {
+        Operation::Compare { i, j } => format!("compare {i} {j}"),
+        Operation::Swap { i, j } => format!("swap {i} {j}"),
+        Operation::Write { index, value } => format!("write {index} = {value}"),
+        Operation::MarkSorted { index } => format!("mark_sorted {index}"),
+        Operation::EnterRange { start, end } => format!("enter_range {start}..{end}"),
+        Operation::LeaveRange { start, end } => format!("leave_range {start}..{end}"),
+        Operation::Phase { phase } => format!("phase {}", phase_name(phase)),
+        Operation::HeapBoundary { end } => format!("heap_boundary {end}"),
+    }
+}
+
+fn join(numbers: &[i64]) -> String {
+    numbers.iter().map(i64::to_string).collect::<Vec<_>>().join(" ")
+}
+
+/// Cells as `row:col`, separated by spaces.
+fn join_cells(cells: &[Cell]) -> String {
+    cells.iter().map(|(row, col)| format!("{row}:{col}")).collect::<Vec<_>>().join(" ")
+}
@@ -7,6 +7,11 @@ type SortingOption = {
   label: string;
 };
 
+type AlgorithmInfo = {
+  slug: string;
+  name: string;
+};
+
 interface SelectSortingAlgorithmProps {
   handleAlgorithmSelection: (algorithm: string) => void;
 }
@@ -25,9 +30,9 @@ export default ({ handleAlgorithmSelection }: SelectSortingAlgorithmProps) => {
     fetch('http://127.0.0.1:8000/sort/algorithms')
       .then(response => response.json())
       .then(data => {
-        const formattedData = data.map((algorithm: string) => ({
-          value: algorithm,
-          label: algorithm,
+        const formattedData = data.map((algorithm: AlgorithmInfo) => ({
+          value: algorithm.slug,
+          label: algorithm.name,
         }));
         setOptions(formattedData);
       });
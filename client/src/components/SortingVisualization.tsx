@@ -6,20 +6,53 @@ import SortingMenu from './SortingMenu';
 
 const apiService = new ApiService('http://localhost:8000');
 
+export type SortOperation =
+    | { op: 'compare'; i: number; j: number }
+    | { op: 'swap'; i: number; j: number }
+    | { op: 'write'; index: number; value: number }
+    | { op: 'mark_sorted'; index: number }
+    | { op: 'enter_range'; start: number; end: number }
+    | { op: 'leave_range'; start: number; end: number }
+    | { op: 'phase'; phase: 'build_heap' | 'extract_max' }
+    | { op: 'heap_boundary'; end: number };
+
 export type SortedNumbersResponse = {
-    array_accesses: number;
+    metrics: {
+      comparisons: number;
+      swaps: number;
+      reads: number;
+      writes: number;
+      auxiliary_memory: number;
+    };
     duration: {
       nanos: number;
       secs: number;
     };
     result: number[];
+    operations: SortOperation[];
     results_length: number;
     status: string;
   };
 
+// Replays the operation trace over the input and keeps a snapshot after every array mutation.
+const replayOperations = (numbers: number[], operations: SortOperation[]): number[][] => {
+    const current = [...numbers];
+    const history = [[...current]];
+    operations.forEach(operation => {
+        if (operation.op === 'swap') {
+            [current[operation.i], current[operation.j]] = [current[operation.j], current[operation.i]];
+            history.push([...current]);
+        } else if (operation.op === 'write') {
+            current[operation.index] = operation.value;
+            history.push([...current]);
+        }
+    });
+    return history;
+};
+
 const SortingVisualization: React.FC = () => {
     const [numbers, setNumbers] = useState<number[]>([]);
-    const [sortedNumbers, setSortedNumbers] = useState<SortedNumbersResponse | null>(null);
+    const [history, setHistory] = useState<number[][]>([]);
     const [width, setWidth] = useState(window.innerWidth * 0.8);
     const [height, setHeight] = useState(window.innerHeight * 0.8);
     const [selectedAlgorithm, setSelectedAlgorithm] = useState<string>('');
@@ -41,11 +74,11 @@ const SortingVisualization: React.FC = () => {
     const handleAlgorithmSelection = (algorithm: string) => {
         setSelectedAlgorithm(algorithm);
         apiService.performSortingAlgorithm(algorithm, numbers)
-            .then(sortedNumbersResult => {
-                setSortedNumbers(sortedNumbersResult);
-                console.log('sortedNumbersResult.result', sortedNumbersResult.result.length);
-                console.log('sortedNumbersResult.results_length', sortedNumbersResult.results_length);
-                let maxStep = sortedNumbersResult.result.length / sortedNumbersResult.results_length
+            .then((sortedNumbersResult: SortedNumbersResponse) => {
+                const replayed = replayOperations(numbers, sortedNumbersResult.operations);
+                setHistory(replayed);
+                console.log('sortedNumbersResult.operations', sortedNumbersResult.operations.length);
+                let maxStep = replayed.length;
                 setMaxStep(maxStep);
                 console.log('maxstep', maxStep);
             })
@@ -55,7 +88,7 @@ const SortingVisualization: React.FC = () => {
     return (
         <div className='background background-color content'>
             <div>
-                <BarGraph sortedNumbersResponse={sortedNumbers} width={width} height={height} step={step}/>
+                <BarGraph numbers={history[Math.max(step - 1, 0)] ?? []} width={width} height={height}/>
             </div>
             <div>
                 <SortingMenu setNumbers={setNumbers} handleAlgorithmSelection={handleAlgorithmSelection} setStep={setStep} step={step} maxStep={maxStep} selectedAlgorithm={selectedAlgorithm}/>
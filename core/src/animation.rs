@@ -0,0 +1,248 @@
+//! Self-contained animated SVG and GIF renderings of [`Steps`], for places
+//! that cannot run the app itself, such as slides and LMS pages.
+
+use std::fmt::Write;
+use std::str::FromStr;
+
+use crate::gif::GifWriter;
+use crate::steps::{BarColor, Steps};
+
+const MARGIN: u16 = 10;
+/// How long the sorted array stays up before the animation starts over.
+const HOLD_MS: u32 = 1500;
+pub(crate) const BACKGROUND: (u8, u8, u8) = (255, 255, 255);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationFormat {
+    Svg,
+    Gif,
+}
+
+impl AnimationFormat {
+    #[must_use]
+    pub fn extension(self) -> &'static str {
+        match self {
+            AnimationFormat::Svg => "svg",
+            AnimationFormat::Gif => "gif",
+        }
+    }
+
+    #[must_use]
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            AnimationFormat::Svg => "image/svg+xml",
+            AnimationFormat::Gif => "image/gif",
+        }
+    }
+}
+
+impl FromStr for AnimationFormat {
+    type Err = &'static str;
+
+    fn from_str(extension: &str) -> Result<Self, Self::Err> {
+        match extension {
+            "svg" => Ok(AnimationFormat::Svg),
+            "gif" => Ok(AnimationFormat::Gif),
+            _ => Err("Animations are available as svg or gif."),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnimationOptions {
+    pub width: u16,
+    pub height: u16,
+    /// How long each step is shown, in milliseconds.
+    pub frame_ms: u32,
+}
+
+impl Default for AnimationOptions {
+    fn default() -> Self {
+        AnimationOptions { width: 640, height: 360, frame_ms: 100 }
+    }
+}
+
+/// Renders the steps in `format`.
+#[must_use]
+pub fn render(format: AnimationFormat, steps: &Steps, options: AnimationOptions) -> Vec<u8> {
+    match format {
+        AnimationFormat::Svg => svg(steps, options).into_bytes(),
+        AnimationFormat::Gif => gif(steps, options),
+    }
+}
+
+/// Where the bars go on the canvas, shared by every format so they match
+/// pixel for pixel.
+pub(crate) struct Layout {
+    left: u16,
+    top: u16,
+    width: u16,
+    height: u16,
+    count: usize,
+    low: f64,
+    high: f64,
+}
+
+impl Layout {
+    /// Fits `count` bars into the canvas, scaled so every one of `values`
+    /// fits along with the zero line.
+    pub(crate) fn new<'a>(values: impl IntoIterator<Item = &'a f64>, count: usize, width: u16, height: u16) -> Self {
+        let (low, high) = values.into_iter().fold((0.0, 0.0), |(low, high): (f64, f64), &value| (low.min(value), high.max(value)));
+        Layout {
+            left: MARGIN,
+            top: MARGIN,
+            width: width.saturating_sub(2 * MARGIN).max(1),
+            height: height.saturating_sub(2 * MARGIN).max(1),
+            count: count.max(1),
+            low,
+            high: if high > low { high } else { low + 1.0 },
+        }
+    }
+
+    fn for_steps(steps: &Steps, options: AnimationOptions) -> Self {
+        Layout::new(steps.steps.iter().flatten(), steps.steps.first().map_or(0, Vec::len), options.width, options.height)
+    }
+
+    /// Left edge and width of the bar at `index`, leaving a pixel between
+    /// bars when there is room for one.
+    pub(crate) fn column(&self, index: usize) -> (u16, u16) {
+        let edge = |index: usize| u16::try_from(index * usize::from(self.width) / self.count).unwrap_or(self.width);
+        let (start, end) = (edge(index), edge(index + 1));
+        let gap = u16::from(end - start >= 4);
+        (self.left + start, (end - start).saturating_sub(gap).max(1))
+    }
+
+    /// Top edge and height of a bar, drawn from the zero line up or down.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub(crate) fn bar(&self, value: f64) -> (u16, u16) {
+        let y = |value: f64| self.top + ((self.high - value) / (self.high - self.low) * f64::from(self.height)).round() as u16;
+        let (a, b) = (y(value), y(0.0));
+        (a.min(b), a.abs_diff(b))
+    }
+}
+
+pub(crate) fn hex((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+fn duration_ms(steps: &Steps, options: AnimationOptions) -> u32 {
+    u32::try_from(steps.len()).unwrap_or(u32::MAX).saturating_mul(options.frame_ms).saturating_add(HOLD_MS)
+}
+
+/// An SVG whose bars change height and colour through SMIL animations. Each
+/// attribute only lists the steps where it changes, so the file grows with
+/// the number of changes rather than steps times bars.
+#[must_use]
+pub fn svg(steps: &Steps, options: AnimationOptions) -> String {
+    let layout = Layout::for_steps(steps, options);
+    let (width, height) = (options.width, options.height);
+    let duration = duration_ms(steps, options);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">\n<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n",
+        hex(BACKGROUND),
+    );
+    let Some(first) = steps.steps.first() else {
+        svg.push_str("</svg>\n");
+        return svg;
+    };
+
+    for index in 0..first.len() {
+        let (x, bar_width) = layout.column(index);
+        let bars: Vec<(u16, u16)> = steps.steps.iter().map(|step| layout.bar(step[index])).collect();
+        let fills: Vec<String> = steps.palette.iter().map(|palette| hex(palette[index].rgb())).collect();
+        let _ = write!(
+            svg,
+            "<rect x=\"{x}\" y=\"{}\" width=\"{bar_width}\" height=\"{}\" fill=\"{}\">",
+            bars[0].0, bars[0].1, fills[0],
+        );
+        let tops: Vec<String> = bars.iter().map(|bar| bar.0.to_string()).collect();
+        let heights: Vec<String> = bars.iter().map(|bar| bar.1.to_string()).collect();
+        for (attribute, values) in [("y", &tops), ("height", &heights), ("fill", &fills)] {
+            svg.push_str(&animate(attribute, values, options.frame_ms, duration));
+        }
+        svg.push_str("</rect>\n");
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// A discrete `<animate>` switching to each value at its step, or nothing
+/// if the value never changes.
+#[allow(clippy::cast_precision_loss)]
+fn animate(attribute: &str, values: &[String], frame_ms: u32, duration: u32) -> String {
+    let mut changes = vec![(0, &values[0])];
+    for (step, value) in values.iter().enumerate().skip(1) {
+        if value != &values[step - 1] {
+            changes.push((step, value));
+        }
+    }
+    if changes.len() == 1 {
+        return String::new();
+    }
+    let times: Vec<String> = changes
+        .iter()
+        .map(|&(step, _)| format!("{:.6}", (step as f64 * f64::from(frame_ms)) / f64::from(duration)))
+        .collect();
+    let values: Vec<&str> = changes.iter().map(|(_, value)| value.as_str()).collect();
+    format!(
+        "<animate attributeName=\"{attribute}\" values=\"{}\" keyTimes=\"{}\" dur=\"{duration}ms\" calcMode=\"discrete\" repeatCount=\"indefinite\"/>",
+        values.join(";"),
+        times.join(";"),
+    )
+}
+
+fn color_index(color: BarColor) -> u8 {
+    match color {
+        BarColor::Green => 1,
+        BarColor::Grey => 2,
+        BarColor::Orange => 3,
+        BarColor::Red => 4,
+    }
+}
+
+/// A looping GIF. After the first frame only the columns that changed are
+/// redrawn, which keeps long runs small.
+#[must_use]
+pub fn gif(steps: &Steps, options: AnimationOptions) -> Vec<u8> {
+    let layout = Layout::for_steps(steps, options);
+    let palette = [BACKGROUND, BarColor::Green.rgb(), BarColor::Grey.rgb(), BarColor::Orange.rgb(), BarColor::Red.rgb()];
+    let mut gif = GifWriter::new(options.width, options.height, &palette);
+    let delay = u16::try_from(options.frame_ms / 10).unwrap_or(u16::MAX).max(2);
+    let last_delay = delay.saturating_add(u16::try_from(HOLD_MS / 10).unwrap_or(u16::MAX));
+
+    for (step, (values, colors)) in steps.steps.iter().zip(&steps.palette).enumerate() {
+        let delay = if step + 1 == steps.len() { last_delay } else { delay };
+        let changed = if step == 0 {
+            Some((0, values.len()))
+        } else {
+            let previous = (&steps.steps[step - 1], &steps.palette[step - 1]);
+            let differs = |&index: &usize| layout.bar(values[index]) != layout.bar(previous.0[index]) || colors[index] != previous.1[index];
+            let first = (0..values.len()).find(differs);
+            first.map(|first| (first, (first..values.len()).rfind(differs).unwrap_or(first) + 1))
+        };
+
+        let (left, top, width, height) = match changed {
+            _ if step == 0 => (0, 0, options.width, options.height),
+            Some((first, end)) => {
+                let (left, _) = layout.column(first);
+                let (last, last_width) = layout.column(end - 1);
+                (left, layout.top, last + last_width - left, layout.height + 1)
+            }
+            // nothing to redraw, but the frame still has to take its time
+            None => (0, 0, 1, 1),
+        };
+        let mut pixels = vec![0u8; usize::from(width) * usize::from(height)];
+        if let Some((first, end)) = changed {
+            for index in first..end {
+                let (x, bar_width) = layout.column(index);
+                let (y, bar_height) = layout.bar(values[index]);
+                for row in y..(y + bar_height).min(top + height) {
+                    let start = usize::from(row - top) * usize::from(width) + usize::from(x - left);
+                    pixels[start..start + usize::from(bar_width)].fill(color_index(colors[index]));
+                }
+            }
+        }
+        gif.frame(left, top, width, height, delay, &pixels);
+    }
+    gif.finish()
+}
@@ -0,0 +1,122 @@
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use crate::sorting::AlgorithmType;
+
+/// What a client needs to know to present a sorting algorithm; the single
+/// place this is written down.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct AlgorithmInfo {
+    /// The path segment the algorithm is served under, e.g. `/sort/quick`.
+    pub slug: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub best_time: &'static str,
+    pub average_time: &'static str,
+    pub worst_time: &'static str,
+    pub space: &'static str,
+    /// Equal elements keep their relative order.
+    pub stable: bool,
+    /// Needs no more than a constant or logarithmic amount of extra memory.
+    pub in_place: bool,
+    /// Runs faster on input that is already partly sorted.
+    pub adaptive: bool,
+    pub applications: &'static str,
+    /// The lines steps point into, see [`crate::pseudocode`].
+    pub pseudocode: &'static [&'static str],
+}
+
+impl AlgorithmType {
+    #[must_use]
+    pub fn info(self) -> AlgorithmInfo {
+        let slug = self.slug();
+        let pseudocode = self.pseudocode();
+        match self {
+            AlgorithmType::Selection => AlgorithmInfo {
+                slug,
+                pseudocode,
+                name: "Selection Sort",
+                description: "Selection Sort divides the input into a sorted and an unsorted region. It repeatedly finds the minimum element from the unsorted region and adds it to the end of the sorted region. Like Bubble Sort, it's primarily used for educational purposes and small datasets where minimizing the number of swaps is important. It performs the same number of comparisons regardless of the initial order of elements, making it inefficient for large datasets.",
+                best_time: "O(n²)",
+                average_time: "O(n²)",
+                worst_time: "O(n²)",
+                space: "O(1)",
+                stable: false,
+                in_place: true,
+                adaptive: false,
+                applications: "Small datasets and educational purposes",
+            },
+            AlgorithmType::Bubble => AlgorithmInfo {
+                slug,
+                pseudocode,
+                name: "Bubble Sort",
+                description: "Bubble Sort is a simple sorting algorithm that repeatedly steps through the list, compares adjacent elements and swaps them if they are in the wrong order. The algorithm gets its name because smaller elements 'bubble' to the top of the list with each iteration. This algorithm is mainly used for educational purposes and is practical only for small data sets where simple implementation is more important than efficiency. Its straightforward nature makes it an excellent teaching tool for introducing the concept of sorting algorithms.",
+                best_time: "O(n)",
+                average_time: "O(n²)",
+                worst_time: "O(n²)",
+                space: "O(1)",
+                stable: true,
+                in_place: true,
+                adaptive: true,
+                applications: "Educational purposes and small datasets",
+            },
+            AlgorithmType::Insertion => AlgorithmInfo {
+                slug,
+                pseudocode,
+                name: "Insertion Sort",
+                description: "Insertion Sort builds the final sorted array one item at a time by iterating through the input array and shifting larger elements to the right to make room for the current element being inserted. It's highly efficient for small and nearly sorted arrays. The algorithm's performance significantly improves when dealing with partially sorted data, making it a practical choice for maintaining sorted lists or sorting small chunks of data within larger algorithms.",
+                best_time: "O(n)",
+                average_time: "O(n²)",
+                worst_time: "O(n²)",
+                space: "O(1)",
+                stable: true,
+                in_place: true,
+                adaptive: true,
+                applications: "Small datasets and nearly sorted arrays",
+            },
+            AlgorithmType::Merge => AlgorithmInfo {
+                slug,
+                pseudocode,
+                name: "Merge Sort",
+                description: "Merge Sort divides the array into halves down to single elements, then merges these parts back together in sorted order. During merging, it compares elements from both parts and combines them in order, creating increasingly larger sorted segments. It guarantees consistent performance regardless of input order and is particularly efficient for large datasets. The algorithm is stable and predictable but requires additional memory space.",
+                best_time: "O(n log n)",
+                average_time: "O(n log n)",
+                worst_time: "O(n log n)",
+                space: "O(n)",
+                stable: true,
+                in_place: false,
+                adaptive: false,
+                applications: "Large datasets, external sorting, stable sorting requirements",
+            },
+            AlgorithmType::Quick => AlgorithmInfo {
+                slug,
+                pseudocode,
+                name: "Quick Sort",
+                description: "Quick Sort is based on the divide-and-conquer strategy. It selects a 'pivot' element and partitions the array around it, with smaller elements going to one side and larger elements to the other. The pivot then goes to its final position, and the process is recursively repeated for both sub-arrays. This algorithm is widely used in practice due to its efficient average-case performance and in-place sorting capability.",
+                best_time: "O(n log n)",
+                average_time: "O(n log n)",
+                worst_time: "O(n²)",
+                space: "O(log n)",
+                stable: false,
+                in_place: true,
+                adaptive: false,
+                applications: "General-purpose sorting, standard library implementations",
+            },
+            AlgorithmType::Heap => AlgorithmInfo {
+                slug,
+                pseudocode,
+                name: "Heap Sort",
+                description: "Heap Sort first rearranges the array into a binary max-heap, a tree stored in the array itself in which every parent is at least as large as its children. It then repeatedly swaps the largest element at the root with the last element of the heap, shrinks the heap by one and sifts the new root down until the heap property holds again. It guarantees O(n log n) time on any input without extra memory, at the cost of stability and of a memory access pattern that is less cache friendly than Quick Sort's.",
+                best_time: "O(n log n)",
+                average_time: "O(n log n)",
+                worst_time: "O(n log n)",
+                space: "O(1)",
+                stable: false,
+                in_place: true,
+                adaptive: false,
+                applications: "Systems that need guaranteed worst-case time and constant memory, priority queues",
+            },
+        }
+    }
+}
@@ -0,0 +1,267 @@
+//! Just enough of `GIF89a` to write looping animations with a fixed palette.
+
+use std::collections::HashMap;
+
+const MAX_CODE: u16 = 4096;
+
+pub struct GifWriter {
+    bytes: Vec<u8>,
+    min_code_size: u8,
+}
+
+impl GifWriter {
+    /// Starts an endlessly looping animation. `palette` holds at most 256
+    /// colours; frames refer to them by index.
+    pub fn new(width: u16, height: u16, palette: &[(u8, u8, u8)]) -> Self {
+        // the colour table has a power of two entries, at least four so the
+        // LZW minimum code size is valid
+        let bits = palette.len().next_power_of_two().trailing_zeros().clamp(2, 8);
+        let mut bytes = b"GIF89a".to_vec();
+        bytes.extend(width.to_le_bytes());
+        bytes.extend(height.to_le_bytes());
+        bytes.extend([0xF0 | u8::try_from(bits - 1).unwrap_or(7), 0, 0]);
+        for index in 0..1 << bits {
+            let (r, g, b) = palette.get(index).copied().unwrap_or_default();
+            bytes.extend([r, g, b]);
+        }
+        bytes.extend(b"\x21\xFF\x0BNETSCAPE2.0\x03\x01\x00\x00\x00");
+        GifWriter { bytes, min_code_size: u8::try_from(bits).unwrap_or(8) }
+    }
+
+    /// Draws `pixels`, row-major palette indices, over the rectangle at
+    /// `left`/`top` of the previous frame and shows it for `delay`
+    /// hundredths of a second.
+    pub fn frame(&mut self, left: u16, top: u16, width: u16, height: u16, delay: u16, pixels: &[u8]) {
+        // graphic control extension: keep the previous frame under this one
+        self.bytes.extend([0x21, 0xF9, 0x04, 0x04]);
+        self.bytes.extend(delay.to_le_bytes());
+        self.bytes.extend([0, 0]);
+
+        self.bytes.push(0x2C);
+        for value in [left, top, width, height] {
+            self.bytes.extend(value.to_le_bytes());
+        }
+        self.bytes.push(0);
+
+        self.bytes.push(self.min_code_size);
+        for block in lzw(self.min_code_size, pixels).chunks(255) {
+            self.bytes.push(u8::try_from(block.len()).unwrap_or(u8::MAX));
+            self.bytes.extend(block);
+        }
+        self.bytes.push(0);
+    }
+
+    pub fn finish(mut self) -> Vec<u8> {
+        self.bytes.push(0x3B);
+        self.bytes
+    }
+}
+
+/// Packs codes of varying width least significant bit first.
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    filled: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, width: u8) {
+        self.buffer |= u32::from(code) << self.filled;
+        self.filled += width;
+        while self.filled >= 8 {
+            self.bytes.push(self.buffer.to_le_bytes()[0]);
+            self.buffer >>= 8;
+            self.filled -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.filled > 0 {
+            self.bytes.push(self.buffer.to_le_bytes()[0]);
+        }
+        self.bytes
+    }
+}
+
+fn lzw(min_code_size: u8, pixels: &[u8]) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+    let mut out = BitWriter { bytes: Vec::new(), buffer: 0, filled: 0 };
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut width = min_code_size + 1;
+    let mut next = clear + 2;
+
+    out.write(clear, width);
+    let Some((&first, rest)) = pixels.split_first() else {
+        out.write(end, width);
+        return out.finish();
+    };
+    let mut prefix = u16::from(first);
+    for &pixel in rest {
+        if let Some(&code) = table.get(&(prefix, pixel)) {
+            prefix = code;
+            continue;
+        }
+        out.write(prefix, width);
+        if next < MAX_CODE {
+            table.insert((prefix, pixel), next);
+            // the decoder adds each entry one code later, so it widens its
+            // codes exactly when this entry's number no longer fits
+            if next >= 1 << width && width < 12 {
+                width += 1;
+            }
+            next += 1;
+        } else {
+            out.write(clear, width);
+            table.clear();
+            width = min_code_size + 1;
+            next = clear + 2;
+        }
+        prefix = u16::from(pixel);
+    }
+    out.write(prefix, width);
+    out.write(end, width);
+    out.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Image {
+        rectangle: [u16; 4],
+        delay: u16,
+        pixels: Vec<u8>,
+    }
+
+    /// The images in `bytes`, decoded by the book rather than by mirroring
+    /// the writer.
+    fn decode(bytes: &[u8]) -> Vec<Image> {
+        assert_eq!(&bytes[..6], b"GIF89a");
+        let word = |at: usize| u16::from_le_bytes([bytes[at], bytes[at + 1]]);
+        let mut at = 13 + 3 * (2 << (bytes[10] & 7));
+        let mut delay = 0;
+        let mut images = Vec::new();
+        loop {
+            match bytes[at] {
+                0x21 => {
+                    if bytes[at + 1] == 0xF9 {
+                        delay = word(at + 4);
+                    }
+                    at += 2;
+                    while bytes[at] != 0 {
+                        at += usize::from(bytes[at]) + 1;
+                    }
+                    at += 1;
+                }
+                0x2C => {
+                    let rectangle = [word(at + 1), word(at + 3), word(at + 5), word(at + 7)];
+                    let min_code_size = bytes[at + 10];
+                    at += 11;
+                    let mut data = Vec::new();
+                    while bytes[at] != 0 {
+                        let length = usize::from(bytes[at]);
+                        data.extend(&bytes[at + 1..=at + length]);
+                        at += length + 1;
+                    }
+                    at += 1;
+                    images.push(Image { rectangle, delay, pixels: unlzw(min_code_size, &data) });
+                }
+                0x3B => return images,
+                block => panic!("unexpected block {block:#04x} at byte {at}"),
+            }
+        }
+    }
+
+    fn unlzw(min_code_size: u8, data: &[u8]) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        // the clear and end codes take up two entries that never match
+        let initial: Vec<Vec<u8>> = (0..=u8::MAX).take(clear).map(|index| vec![index]).chain([Vec::new(), Vec::new()]).collect();
+        let mut table = initial.clone();
+        let mut width = min_code_size + 1;
+        let mut previous: Option<Vec<u8>> = None;
+        let (mut buffer, mut filled, mut data) = (0u32, 0u8, data.iter());
+        let mut pixels = Vec::new();
+        loop {
+            while filled < width {
+                buffer |= u32::from(*data.next().expect("the data ends before the end code")) << filled;
+                filled += 8;
+            }
+            let code = usize::try_from(buffer & ((1 << width) - 1)).unwrap();
+            buffer >>= width;
+            filled -= width;
+
+            if code == clear {
+                table.clone_from(&initial);
+                width = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == clear + 1 {
+                return pixels;
+            }
+            let entry = match (table.get(code), &previous) {
+                (Some(entry), _) => entry.clone(),
+                // the code the encoder added right before writing it
+                (None, Some(previous)) if code == table.len() => [previous.as_slice(), &previous[..1]].concat(),
+                _ => panic!("code {code} is not in the table"),
+            };
+            pixels.extend(&entry);
+            if let Some(previous) = previous {
+                if table.len() < usize::from(MAX_CODE) {
+                    table.push([previous.as_slice(), &entry[..1]].concat());
+                }
+            }
+            if table.len() == 1 << width && width < 12 {
+                width += 1;
+            }
+            previous = Some(entry);
+        }
+    }
+
+    /// Deterministic palette indices below `colours`.
+    fn noise(length: usize, colours: u8) -> Vec<u8> {
+        let mut state: u32 = 12345;
+        (0..length)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                state.to_be_bytes()[0] % colours
+            })
+            .collect()
+    }
+
+    #[test]
+    fn frames_round_trip_through_a_decoder() {
+        let palette: Vec<(u8, u8, u8)> = (0..=u8::MAX).map(|index| (index, index, index)).collect();
+        let frames = [
+            // enough distinct strings to fill the code table several times, even in two colours
+            ([0, 0, 400, 300], 4, noise(400 * 300, 2)),
+            ([0, 0, 400, 300], 5, noise(400 * 300, 7)),
+            ([10, 20, 100, 60], 6, noise(100 * 60, u8::MAX)),
+            ([0, 0, 400, 300], 7, vec![3; 400 * 300]),
+            ([5, 5, 1, 1], 8, vec![1]),
+            ([0, 0, 0, 0], 9, Vec::new()),
+        ];
+        for colours in [2, 5, 16, 256] {
+            let mut gif = GifWriter::new(400, 300, &palette[..colours]);
+            let frames: Vec<_> = frames
+                .iter()
+                .map(|([left, top, width, height], delay, pixels)| {
+                    let limit = u8::try_from(colours - 1).unwrap();
+                    let pixels: Vec<u8> = pixels.iter().map(|&pixel| pixel.min(limit)).collect();
+                    gif.frame(*left, *top, *width, *height, *delay, &pixels);
+                    ([*left, *top, *width, *height], *delay, pixels)
+                })
+                .collect();
+            let bytes = gif.finish();
+
+            let images = decode(&bytes);
+            assert_eq!(images.len(), frames.len());
+            for (image, (rectangle, delay, pixels)) in images.iter().zip(&frames) {
+                assert_eq!(image.rectangle, *rectangle);
+                assert_eq!(image.delay, *delay);
+                assert!(image.pixels == *pixels, "a {colours} colour frame of {rectangle:?} decodes differently");
+            }
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! The text inputs the command line and the terminal player both read.
+
+#[cfg(feature = "serde")]
+use crate::pathfinding::GridSpec;
+
+/// Integers separated by commas or whitespace; a surrounding `[...]` is
+/// allowed so JSON arrays work too.
+///
+/// # Errors
+///
+/// Returns a message naming the first value that is not an integer, or
+/// saying there were no values at all.
+pub fn parse_numbers(input: &str) -> Result<Vec<i64>, String> {
+    let input = input.trim();
+    let input = input.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')).unwrap_or(input);
+    let numbers = input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|number| !number.is_empty())
+        .map(|number| number.parse().map_err(|_| format!("not an integer: {number:?}")))
+        .collect::<Result<Vec<i64>, String>>()?;
+    if numbers.is_empty() {
+        return Err("no numbers to sort".to_string());
+    }
+    Ok(numbers)
+}
+
+/// A JSON grid spec if the input starts with `{`, a text map as read by
+/// [`GridSpec::from_map`] otherwise.
+///
+/// # Errors
+///
+/// Returns a message if the JSON or the map is invalid.
+#[cfg(feature = "serde")]
+pub fn parse_grid(input: &str) -> Result<GridSpec, String> {
+    if input.trim_start().starts_with('{') {
+        serde_json::from_str(input).map_err(|err| format!("invalid grid JSON: {err}"))
+    } else {
+        GridSpec::from_map(input)
+    }
+}
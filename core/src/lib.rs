@@ -0,0 +1,19 @@
+#![warn(clippy::all, clippy::pedantic)]
+//! The sorting and pathfinding algorithms behind the visualisations, shared
+//! by the server, the command line and the Leptos app so all of them run
+//! exactly the same code.
+//!
+//! Enable the `serde` feature to serialize traces and algorithm metadata.
+
+pub mod animation;
+pub mod catalog;
+mod gif;
+pub mod input;
+pub mod narration;
+pub mod pathfinding;
+pub mod pseudocode;
+pub mod replay;
+pub mod snapshot;
+pub mod sorting;
+pub mod steps;
+pub mod trace;
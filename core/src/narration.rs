@@ -0,0 +1,186 @@
+//! Plain-language explanations of what each recorded operation does, for
+//! following a run without knowing the algorithm yet.
+//!
+//! Like [`crate::pseudocode`], this reads the trace alone, so the wording
+//! follows the implementations in [`crate::sorting`].
+
+use crate::replay::{is_visible, Frame};
+use crate::sorting::AlgorithmType;
+use crate::trace::Operation;
+
+/// The explanation of every operation in a trace `algorithm` recorded from
+/// `values`, or `None` where there is nothing to add, such as entering a
+/// range or the sorted marks a finished run hands out.
+#[must_use]
+pub fn narrate(algorithm: AlgorithmType, values: Vec<i64>, operations: &[Operation]) -> Vec<Option<String>> {
+    let mut narrator = Narrator::new(algorithm, values);
+    operations
+        .iter()
+        .enumerate()
+        .map(|(position, operation)| {
+            let next = operations.get(position + 1);
+            let previous = position.checked_sub(1).map(|previous| &operations[previous]);
+            narrator.narrate(operation, previous, next)
+        })
+        .collect()
+}
+
+/// The explanation of every step a replay of the trace stops at: how the
+/// run starts, one per visible operation, and how it ends. Notes on the
+/// invisible operations are told together with the step before them.
+#[must_use]
+pub fn narrate_steps(algorithm: AlgorithmType, values: Vec<i64>, operations: &[Operation]) -> Vec<String> {
+    let count = values.len();
+    let mut steps = vec![format!("Starting {} on {count} values.", algorithm.info().name)];
+    for (operation, note) in operations.iter().zip(narrate(algorithm, values, operations)) {
+        if is_visible(operation) {
+            steps.push(note.unwrap_or_default());
+        } else if let (Some(note), Some(last)) = (note, steps.last_mut()) {
+            // e.g. a pivot marked sorted, told together with the step that placed it
+            last.push(' ');
+            last.push_str(&note);
+        }
+    }
+    steps.push(format!("All {count} values are sorted."));
+    steps
+}
+
+/// Replays the trace alongside the explanations, since they quote the
+/// values as they stood before each operation.
+struct Narrator {
+    algorithm: AlgorithmType,
+    frame: Frame,
+    /// The value insertion sort is finding a place for.
+    key: Option<i64>,
+    /// The range merge sort is merging, as it stood before the merge began
+    /// writing back over it.
+    merging: Option<(usize, Vec<i64>)>,
+}
+
+impl Narrator {
+    fn new(algorithm: AlgorithmType, values: Vec<i64>) -> Self {
+        Narrator { algorithm, frame: Frame::new(values), key: None, merging: None }
+    }
+
+    fn narrate(&mut self, operation: &Operation, previous: Option<&Operation>, next: Option<&Operation>) -> Option<String> {
+        match *operation {
+            // a merge only starts once both halves are done, after their ranges were left
+            Operation::EnterRange { .. } | Operation::LeaveRange { .. } => self.merging = None,
+            Operation::Compare { .. } | Operation::Write { .. } if self.algorithm == AlgorithmType::Merge && self.merging.is_none() => {
+                self.merging = self.frame.innermost_range().map(|(start, end)| (start, self.frame.values[start..end].to_vec()));
+            }
+            Operation::Compare { j, .. } if self.algorithm == AlgorithmType::Insertion => {
+                // the key stays out of the array while larger values shift into its hole
+                let shifting = matches!(previous, Some(&Operation::Write { index, .. }) if index == j + 1);
+                if !shifting {
+                    self.key = Some(self.frame.values[j]);
+                }
+            }
+            _ => {}
+        }
+        let text = self.describe(operation, previous, next);
+        self.frame.apply(operation);
+        text
+    }
+
+    fn value(&self, index: usize) -> i64 {
+        self.frame.values[index]
+    }
+
+    fn describe(&self, operation: &Operation, previous: Option<&Operation>, next: Option<&Operation>) -> Option<String> {
+        let swaps_next = |i: usize, j: usize| matches!(next, Some(&Operation::Swap { i: a, j: b }) if (a, b) == (i, j) || (a, b) == (j, i));
+        let text = match (self.algorithm, operation) {
+            (AlgorithmType::Bubble, &Operation::Compare { i, j }) => {
+                let (left, right) = (self.value(i), self.value(j));
+                if swaps_next(i, j) {
+                    format!("Comparing {left} and {right}; {left} is larger, so swap them.")
+                } else {
+                    format!("Comparing {left} and {right}; they are already in order.")
+                }
+            }
+            // the sorted marks once the passes stop early follow the last mark, not a pass
+            (AlgorithmType::Bubble, &Operation::MarkSorted { index }) if matches!(previous, Some(Operation::Compare { .. } | Operation::Swap { .. })) => {
+                format!("{} has bubbled up to its final index, {index}.", self.value(index))
+            }
+
+            (AlgorithmType::Selection, &Operation::Compare { i, j }) => {
+                let (candidate, minimum) = (self.value(i), self.value(j));
+                if candidate < minimum {
+                    format!("Comparing {candidate} with the smallest so far, {minimum}; {candidate} is smaller, so it becomes the new minimum.")
+                } else {
+                    format!("Comparing {candidate} with the smallest so far, {minimum}; {minimum} stays the minimum.")
+                }
+            }
+            (AlgorithmType::Selection, &Operation::Swap { i, j }) => {
+                format!("Swapping the minimum, {}, into index {i} in place of {}.", self.value(j), self.value(i))
+            }
+            (AlgorithmType::Selection, &Operation::MarkSorted { index }) => {
+                format!("{} is in its final place at index {index}.", self.value(index))
+            }
+
+            (AlgorithmType::Insertion, &Operation::Compare { i, j }) => {
+                let (previous, key) = (self.value(i), self.key.unwrap_or_else(|| self.value(j)));
+                if previous > key {
+                    format!("Comparing {key} with {previous}; {previous} is larger, so it shifts right.")
+                } else {
+                    format!("Comparing {key} with {previous}; {previous} is not larger, so {key} belongs at index {j}.")
+                }
+            }
+            // a shift leaves the hole one to the left, where the next comparison or the key's write happens
+            (AlgorithmType::Insertion, &Operation::Write { index, value })
+                if matches!(next, Some(&Operation::Compare { j, .. }) if j + 1 == index)
+                    || matches!(next, Some(&Operation::Write { index: hole, .. }) if hole + 1 == index) =>
+            {
+                format!("Shifting {value} right to index {index}.")
+            }
+            (AlgorithmType::Insertion, &Operation::Write { index, value }) => format!("Inserting {value} at index {index}."),
+
+            (AlgorithmType::Merge, &Operation::Compare { i, j }) => {
+                let (start, before) = self.merging.as_ref()?;
+                let (left, right) = (before[i - start], before[j - start]);
+                let taken = if left <= right { left } else { right };
+                format!("Merging: comparing {left} from the left half with {right} from the right half; {taken} comes first.")
+            }
+            (AlgorithmType::Merge, &Operation::Write { index, value }) => format!("Writing {value} back to index {index}."),
+
+            (AlgorithmType::Quick, &Operation::Compare { i, j }) => {
+                let (value, pivot) = (self.value(i), self.value(j));
+                if value < pivot {
+                    format!("Comparing {value} with the pivot {pivot}; {value} is smaller, so it belongs left of the pivot.")
+                } else {
+                    format!("Comparing {value} with the pivot {pivot}; {value} is not smaller, so it stays right of the pivot.")
+                }
+            }
+            (AlgorithmType::Quick, &Operation::Swap { i, j }) if self.frame.pivot(self.algorithm) == Some(j) => {
+                format!("Moving the pivot {} to index {i}, swapping it with {}.", self.value(j), self.value(i))
+            }
+            (AlgorithmType::Quick, &Operation::Swap { i, j }) => {
+                format!("Swapping {} into the smaller part at index {i}, in place of {}.", self.value(j), self.value(i))
+            }
+            // a partition ends in a comparison or in the pivot's swap; anything else marks a range of one
+            (AlgorithmType::Quick, &Operation::MarkSorted { index }) if matches!(previous, Some(Operation::Compare { .. } | Operation::Swap { .. })) => {
+                format!("Pivot {} is placed at its final index, {index}.", self.value(index))
+            }
+
+            (AlgorithmType::Heap, &Operation::Compare { i, j }) => {
+                let (child, largest) = (self.value(i), self.value(j));
+                if child > largest {
+                    format!("Comparing child {child} with {largest}; {child} is larger.")
+                } else {
+                    format!("Comparing child {child} with {largest}; {largest} stays the largest.")
+                }
+            }
+            (AlgorithmType::Heap, &Operation::Swap { i, j }) if matches!(next, Some(Operation::MarkSorted { .. })) => {
+                format!("Moving the maximum, {}, behind the heap to index {j}.", self.value(i))
+            }
+            (AlgorithmType::Heap, &Operation::Swap { i, j }) => {
+                format!("Sifting {} down: swapping it with its larger child, {}.", self.value(i), self.value(j))
+            }
+
+            (_, &Operation::Swap { i, j }) => format!("Swapping {} and {}.", self.value(i), self.value(j)),
+            (_, &Operation::Write { index, value }) => format!("Writing {value} to index {index}."),
+            _ => return None,
+        };
+        Some(text)
+    }
+}
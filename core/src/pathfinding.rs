@@ -0,0 +1,397 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+use std::fmt;
+use std::str::FromStr;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+/// A grid position as `(row, col)`.
+pub type Cell = (usize, usize);
+
+#[derive(EnumIter, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "lowercase"))]
+pub enum PathAlgorithm {
+    Dijkstra,
+    AStar,
+    Bfs,
+    Dfs,
+    Swarm,
+}
+
+impl PathAlgorithm {
+    /// The lowercase name used in routes and on the command line, e.g. `astar`.
+    #[must_use]
+    pub fn slug(self) -> &'static str {
+        match self {
+            PathAlgorithm::Dijkstra => "dijkstra",
+            PathAlgorithm::AStar => "astar",
+            PathAlgorithm::Bfs => "bfs",
+            PathAlgorithm::Dfs => "dfs",
+            PathAlgorithm::Swarm => "swarm",
+        }
+    }
+}
+
+impl FromStr for PathAlgorithm {
+    type Err = &'static str;
+
+    fn from_str(slug: &str) -> Result<Self, Self::Err> {
+        match slug {
+            "dijkstra" => Ok(PathAlgorithm::Dijkstra),
+            "astar" => Ok(PathAlgorithm::AStar),
+            "bfs" => Ok(PathAlgorithm::Bfs),
+            "dfs" => Ok(PathAlgorithm::Dfs),
+            "swarm" => Ok(PathAlgorithm::Swarm),
+            _ => Err("Invalid pathfinding algorithm."),
+        }
+    }
+}
+
+impl fmt::Display for PathAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.slug())
+    }
+}
+
+/// Rectangular grid with 4-connected movement. Entering a cell costs its
+/// weight, which is 1 unless weights were given.
+pub struct Grid {
+    rows: usize,
+    cols: usize,
+    walls: Vec<bool>,
+    weights: Vec<u64>,
+}
+
+impl Grid {
+    #[must_use]
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Grid { rows, cols, walls: vec![false; rows * cols], weights: vec![1; rows * cols] }
+    }
+
+    #[must_use]
+    pub fn contains(&self, (row, col): Cell) -> bool {
+        row < self.rows && col < self.cols
+    }
+
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    #[must_use]
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    fn index(&self, (row, col): Cell) -> usize {
+        row * self.cols + col
+    }
+
+    #[must_use]
+    pub fn is_wall(&self, cell: Cell) -> bool {
+        self.walls[self.index(cell)]
+    }
+
+    pub fn set_wall(&mut self, cell: Cell, wall: bool) {
+        let index = self.index(cell);
+        self.walls[index] = wall;
+    }
+
+    #[must_use]
+    pub fn weight(&self, cell: Cell) -> u64 {
+        self.weights[self.index(cell)]
+    }
+
+    pub fn set_weight(&mut self, cell: Cell, weight: u64) {
+        let index = self.index(cell);
+        self.weights[index] = weight;
+    }
+
+    /// Open neighbours of `cell`, in the order right, down, left, up.
+    fn neighbours(&self, (row, col): Cell) -> impl Iterator<Item = Cell> + '_ {
+        [(0, 1), (1, 0), (0, -1), (-1, 0)]
+            .into_iter()
+            .filter_map(move |(dr, dc)| Some((row.checked_add_signed(dr)?, col.checked_add_signed(dc)?)))
+            .filter(|&cell| self.contains(cell) && !self.is_wall(cell))
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct PathResult {
+    /// Cells in the order they were expanded.
+    pub visited: Vec<Cell>,
+    /// Cells from start to end inclusive; empty when the end is unreachable.
+    pub path: Vec<Cell>,
+    /// Sum of the weights entered along `path`.
+    pub cost: Option<u64>,
+    pub nodes_expanded: usize,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+struct State {
+    cost: u64,
+    position: Cell,
+}
+
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.cmp(&self.cost).then_with(|| other.position.cmp(&self.position))
+    }
+}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Runs `algorithm` from `start` to `end`. Both must lie inside the grid.
+#[must_use]
+pub fn search(algorithm: PathAlgorithm, grid: &Grid, start: Cell, end: Cell) -> PathResult {
+    let (visited, previous) = match algorithm {
+        PathAlgorithm::Dijkstra => best_first(grid, start, end, |_| 0),
+        PathAlgorithm::AStar => best_first(grid, start, end, |cell| manhattan_distance(cell, end)),
+        PathAlgorithm::Bfs => bfs(grid, start, end),
+        PathAlgorithm::Dfs => dfs(grid, start, end),
+        PathAlgorithm::Swarm => swarm(grid, start, end),
+    };
+    let path = reconstruct_path(grid, &previous, start, end);
+    let cost = (!path.is_empty()).then(|| path.iter().skip(1).map(|&cell| grid.weight(cell)).sum());
+    PathResult { nodes_expanded: visited.len(), visited, path, cost }
+}
+
+/// Dijkstra when `heuristic` is zero, A* otherwise. Weights are at least 1, so
+/// the Manhattan distance never overestimates.
+fn best_first(grid: &Grid, start: Cell, end: Cell, heuristic: impl Fn(Cell) -> u64) -> (Vec<Cell>, Vec<Option<Cell>>) {
+    let mut visited = Vec::new();
+    let mut expanded = vec![false; grid.rows * grid.cols];
+    let mut distances = vec![u64::MAX; grid.rows * grid.cols];
+    let mut previous = vec![None; grid.rows * grid.cols];
+    let mut heap = BinaryHeap::new();
+
+    distances[grid.index(start)] = 0;
+    heap.push(State { cost: heuristic(start), position: start });
+
+    while let Some(State { position, .. }) = heap.pop() {
+        if std::mem::replace(&mut expanded[grid.index(position)], true) {
+            continue;
+        }
+        visited.push(position);
+        if position == end {
+            break;
+        }
+        let distance = distances[grid.index(position)];
+        for next in grid.neighbours(position) {
+            let next_distance = distance + grid.weight(next);
+            if next_distance < distances[grid.index(next)] {
+                distances[grid.index(next)] = next_distance;
+                previous[grid.index(next)] = Some(position);
+                heap.push(State { cost: next_distance + heuristic(next), position: next });
+            }
+        }
+    }
+    (visited, previous)
+}
+
+/// Ignores weights: finds the path with the fewest steps.
+fn bfs(grid: &Grid, start: Cell, end: Cell) -> (Vec<Cell>, Vec<Option<Cell>>) {
+    let mut visited = Vec::new();
+    let mut seen = vec![false; grid.rows * grid.cols];
+    let mut previous = vec![None; grid.rows * grid.cols];
+    let mut queue = VecDeque::new();
+
+    seen[grid.index(start)] = true;
+    queue.push_back(start);
+
+    while let Some(position) = queue.pop_front() {
+        visited.push(position);
+        if position == end {
+            break;
+        }
+        for next in grid.neighbours(position) {
+            if !std::mem::replace(&mut seen[grid.index(next)], true) {
+                previous[grid.index(next)] = Some(position);
+                queue.push_back(next);
+            }
+        }
+    }
+    (visited, previous)
+}
+
+/// Ignores weights; the path found is whichever branch reached the end first.
+fn dfs(grid: &Grid, start: Cell, end: Cell) -> (Vec<Cell>, Vec<Option<Cell>>) {
+    let mut visited = Vec::new();
+    let mut expanded = vec![false; grid.rows * grid.cols];
+    let mut previous = vec![None; grid.rows * grid.cols];
+    let mut stack = vec![start];
+
+    while let Some(position) = stack.pop() {
+        if std::mem::replace(&mut expanded[grid.index(position)], true) {
+            continue;
+        }
+        visited.push(position);
+        if position == end {
+            break;
+        }
+        for next in grid.neighbours(position) {
+            if !expanded[grid.index(next)] {
+                previous[grid.index(next)] = Some(position);
+                stack.push(next);
+            }
+        }
+    }
+    (visited, previous)
+}
+
+/// Greedy variant of Dijkstra that inflates every step by half the remaining
+/// distance to the end, so the search is pulled towards it. Not guaranteed to
+/// find the cheapest path.
+fn swarm(grid: &Grid, start: Cell, end: Cell) -> (Vec<Cell>, Vec<Option<Cell>>) {
+    let mut visited = Vec::new();
+    let mut expanded = vec![false; grid.rows * grid.cols];
+    let mut distances = vec![u64::MAX; grid.rows * grid.cols];
+    let mut previous = vec![None; grid.rows * grid.cols];
+    let mut heap = BinaryHeap::new();
+
+    distances[grid.index(start)] = 0;
+    heap.push(State { cost: 0, position: start });
+
+    while let Some(State { position, .. }) = heap.pop() {
+        if std::mem::replace(&mut expanded[grid.index(position)], true) {
+            continue;
+        }
+        visited.push(position);
+        if position == end {
+            break;
+        }
+        let distance = distances[grid.index(position)];
+        for next in grid.neighbours(position) {
+            let next_distance = distance + grid.weight(next) + manhattan_distance(next, end) / 2;
+            if next_distance < distances[grid.index(next)] {
+                distances[grid.index(next)] = next_distance;
+                previous[grid.index(next)] = Some(position);
+                heap.push(State { cost: next_distance, position: next });
+            }
+        }
+    }
+    (visited, previous)
+}
+
+fn manhattan_distance(a: Cell, b: Cell) -> u64 {
+    (a.0.abs_diff(b.0) + a.1.abs_diff(b.1)) as u64
+}
+
+fn reconstruct_path(grid: &Grid, previous: &[Option<Cell>], start: Cell, end: Cell) -> Vec<Cell> {
+    let mut path = vec![end];
+    let mut current = end;
+    while let Some(position) = previous[grid.index(current)] {
+        path.push(position);
+        current = position;
+    }
+    if current != start {
+        return Vec::new();
+    }
+    path.reverse();
+    path
+}
+
+/// Highest cost a cell may have, low enough that no path's cost can overflow.
+pub const MAX_WEIGHT: u64 = 1_000_000;
+
+/// A grid described by its size, walls and endpoints, as posted to the server
+/// or read by the command line.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+pub struct GridSpec {
+    pub rows: usize,
+    pub cols: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub walls: Vec<Cell>,
+    pub start: Cell,
+    pub end: Cell,
+    /// Row-major cost of entering each cell, from 1 to [`MAX_WEIGHT`].
+    pub weights: Option<Vec<Vec<u64>>>,
+}
+
+impl GridSpec {
+    /// Builds the grid, rejecting specs whose cells fall outside it or whose
+    /// weights do not fit.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message saying which part of the spec is invalid.
+    pub fn into_grid(self) -> Result<(Grid, Cell, Cell), &'static str> {
+        if self.rows == 0 || self.cols == 0 {
+            return Err("Grid must have at least one row and one column.");
+        }
+        let mut grid = Grid::new(self.rows, self.cols);
+        if !grid.contains(self.start) || !grid.contains(self.end) {
+            return Err("Start and end must lie inside the grid.");
+        }
+        for &wall in &self.walls {
+            if !grid.contains(wall) {
+                return Err("Walls must lie inside the grid.");
+            }
+            grid.set_wall(wall, true);
+        }
+        if grid.is_wall(self.start) || grid.is_wall(self.end) {
+            return Err("Start and end must not be walls.");
+        }
+        if let Some(weights) = self.weights {
+            if weights.len() != self.rows || weights.iter().any(|row| row.len() != self.cols) {
+                return Err("Weights must match the grid dimensions.");
+            }
+            for (row, values) in weights.into_iter().enumerate() {
+                for (col, weight) in values.into_iter().enumerate() {
+                    if !(1..=MAX_WEIGHT).contains(&weight) {
+                        return Err("Weights must be between 1 and 1000000.");
+                    }
+                    grid.set_weight((row, col), weight);
+                }
+            }
+        }
+        Ok((grid, self.start, self.end))
+    }
+
+    /// Reads a text map with one line per row: `.` is open, `#` a wall, `S`
+    /// the start, `E` the end and `1`-`9` the cost of entering the cell.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message if the rows differ in length, a symbol is unknown
+    /// or the start or end is missing or repeated.
+    pub fn from_map(map: &str) -> Result<GridSpec, String> {
+        let lines: Vec<&str> = map.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+        let cols = lines.first().map_or(0, |line| line.chars().count());
+        if lines.iter().any(|line| line.chars().count() != cols) {
+            return Err("every row of the map must have the same length".to_string());
+        }
+
+        let mut walls = Vec::new();
+        let mut weights = vec![vec![1; cols]; lines.len()];
+        let (mut start, mut end): (Option<Cell>, Option<Cell>) = (None, None);
+        for (row, line) in lines.iter().enumerate() {
+            for (col, symbol) in line.chars().enumerate() {
+                match symbol {
+                    '.' => {}
+                    '#' => walls.push((row, col)),
+                    'S' if start.is_none() => start = Some((row, col)),
+                    'E' if end.is_none() => end = Some((row, col)),
+                    'S' | 'E' => return Err(format!("the map has more than one {symbol:?}")),
+                    '1'..='9' => weights[row][col] = u64::from(symbol.to_digit(10).unwrap_or(1)),
+                    _ => return Err(format!("unexpected {symbol:?} at row {row}, column {col}")),
+                }
+            }
+        }
+        Ok(GridSpec {
+            rows: lines.len(),
+            cols,
+            walls,
+            start: start.ok_or("the map has no start 'S'")?,
+            end: end.ok_or("the map has no end 'E'")?,
+            weights: Some(weights),
+        })
+    }
+}
@@ -0,0 +1,162 @@
+//! Pseudocode for each sorting algorithm, and which of its lines each
+//! recorded operation carries out.
+//!
+//! Line numbers count from 0. Operations are matched to lines using only the
+//! trace itself, so the mapping follows the implementations in
+//! [`crate::sorting`] and has to change with them.
+
+use crate::sorting::AlgorithmType;
+use crate::trace::{Operation, Phase};
+
+const SELECTION: &[&str] = &[
+    "for i in 0..n:",
+    "    min = i",
+    "    for j in i+1..n:",
+    "        if a[j] < a[min]:",
+    "            min = j",
+    "    swap a[i], a[min]",
+    "    mark a[i] sorted",
+];
+
+const BUBBLE: &[&str] = &[
+    "for i in 0..n:",
+    "    swapped = false",
+    "    for j in 0..n-i-1:",
+    "        if a[j] > a[j+1]:",
+    "            swap a[j], a[j+1]",
+    "            swapped = true",
+    "    mark a[n-i-1] sorted",
+    "    if not swapped: stop",
+];
+
+const INSERTION: &[&str] = &[
+    "for i in 1..n:",
+    "    key = a[i]",
+    "    j = i",
+    "    while j > 0 and a[j-1] > key:",
+    "        a[j] = a[j-1]",
+    "        j = j - 1",
+    "    a[j] = key",
+];
+
+const MERGE: &[&str] = &[
+    "merge_sort(a, lo, hi):",
+    "    if hi - lo < 2: return",
+    "    mid = (lo + hi) / 2",
+    "    merge_sort(a, lo, mid)",
+    "    merge_sort(a, mid, hi)",
+    "    left, right = a[lo..mid], a[mid..hi]",
+    "    for k in lo..hi:",
+    "        take the smaller front of left and right",
+    "        a[k] = that element",
+];
+
+const QUICK: &[&str] = &[
+    "quick_sort(a, lo, hi):",
+    "    if lo >= hi: return",
+    "    pivot = a[hi]",
+    "    i = lo",
+    "    for j in lo..hi:",
+    "        if a[j] < pivot:",
+    "            swap a[i], a[j]",
+    "            i = i + 1",
+    "    swap a[i], a[hi]",
+    "    mark a[i] sorted",
+    "    quick_sort the smaller of a[lo..i - 1], a[i + 1..hi]",
+    "    repeat from the top on the larger one",
+];
+
+const HEAP: &[&str] = &[
+    "heap_sort(a):",
+    "    for i in n/2-1 down to 0:",
+    "        sift_down(a, i, n)",
+    "    for end in n-1 down to 1:",
+    "        swap a[0], a[end]",
+    "        mark a[end] sorted",
+    "        sift_down(a, 0, end)",
+    "sift_down(a, i, end):",
+    "    largest = the largest of a[i] and its children before end",
+    "    if largest == i: return",
+    "    swap a[i], a[largest]",
+    "    sift_down(a, largest, end)",
+];
+
+impl AlgorithmType {
+    #[must_use]
+    pub fn pseudocode(self) -> &'static [&'static str] {
+        match self {
+            AlgorithmType::Selection => SELECTION,
+            AlgorithmType::Bubble => BUBBLE,
+            AlgorithmType::Insertion => INSERTION,
+            AlgorithmType::Merge => MERGE,
+            AlgorithmType::Quick => QUICK,
+            AlgorithmType::Heap => HEAP,
+        }
+    }
+}
+
+/// The pseudocode line of every operation in a trace `algorithm` recorded,
+/// or `None` for bookkeeping such as leaving a range.
+#[must_use]
+pub fn lines(algorithm: AlgorithmType, operations: &[Operation]) -> Vec<Option<usize>> {
+    // quick sort's innermost range, to tell the partition swaps from the pivot swap
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    operations
+        .iter()
+        .enumerate()
+        .map(|(position, operation)| {
+            let next = operations.get(position + 1);
+            match *operation {
+                Operation::EnterRange { start, end } => ranges.push((start, end)),
+                Operation::LeaveRange { .. } => {
+                    ranges.pop();
+                }
+                _ => {}
+            }
+            line(algorithm, operation, next, ranges.last().copied())
+        })
+        .collect()
+}
+
+// one arm per algorithm and operation, read as a table
+#[allow(clippy::match_same_arms)]
+fn line(algorithm: AlgorithmType, operation: &Operation, next: Option<&Operation>, range: Option<(usize, usize)>) -> Option<usize> {
+    match (algorithm, operation) {
+        (AlgorithmType::Selection, &Operation::Compare { .. }) => Some(3),
+        (AlgorithmType::Selection, &Operation::Swap { .. }) => Some(5),
+        (AlgorithmType::Selection, &Operation::MarkSorted { .. }) => Some(6),
+
+        (AlgorithmType::Bubble, &Operation::Compare { .. }) => Some(3),
+        (AlgorithmType::Bubble, &Operation::Swap { .. }) => Some(4),
+        (AlgorithmType::Bubble, &Operation::MarkSorted { .. }) => Some(6),
+
+        (AlgorithmType::Insertion, &Operation::Compare { .. }) => Some(3),
+        // a shift is followed by another shift or by the comparison one
+        // position further left; the key is followed by the next key's work
+        (AlgorithmType::Insertion, &Operation::Write { index, .. }) => match next {
+            Some(Operation::Write { .. }) => Some(4),
+            Some(&Operation::Compare { j, .. }) if j + 1 == index => Some(4),
+            _ => Some(6),
+        },
+
+        (AlgorithmType::Merge, &Operation::EnterRange { .. }) => Some(2),
+        (AlgorithmType::Merge, &Operation::Compare { .. }) => Some(7),
+        (AlgorithmType::Merge, &Operation::Write { .. }) => Some(8),
+
+        (AlgorithmType::Quick, &Operation::EnterRange { .. }) => Some(2),
+        (AlgorithmType::Quick, &Operation::Compare { .. }) => Some(5),
+        (AlgorithmType::Quick, &Operation::Swap { j, .. }) if range.is_some_and(|(_, end)| j + 1 == end) => Some(8),
+        (AlgorithmType::Quick, &Operation::Swap { .. }) => Some(6),
+        (AlgorithmType::Quick, &Operation::MarkSorted { .. }) => Some(9),
+
+        (AlgorithmType::Heap, &Operation::Phase { phase: Phase::BuildHeap }) => Some(1),
+        (AlgorithmType::Heap, &Operation::Phase { phase: Phase::ExtractMax }) => Some(3),
+        (AlgorithmType::Heap, &Operation::Compare { .. }) => Some(8),
+        // only moving the maximum out is followed by marking it sorted
+        (AlgorithmType::Heap, &Operation::Swap { .. }) if matches!(next, Some(Operation::MarkSorted { .. })) => Some(4),
+        (AlgorithmType::Heap, &Operation::Swap { .. }) => Some(10),
+        (AlgorithmType::Heap, &Operation::MarkSorted { .. }) => Some(5),
+
+        _ => None,
+    }
+}
@@ -0,0 +1,167 @@
+//! Stepping through a recorded trace, for everything that animates one.
+
+use crate::sorting::AlgorithmType;
+use crate::trace::Operation;
+
+/// The bars as they stand after some prefix of a trace has been applied.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Frame {
+    pub values: Vec<i64>,
+    /// The pair compared most recently.
+    pub comparing: Option<(usize, usize)>,
+    pub sorted: Vec<bool>,
+    /// Half-open ranges the algorithm is working inside, outermost first.
+    pub ranges: Vec<(usize, usize)>,
+    /// End of the heap while heap sort runs; everything past it is sorted.
+    pub heap_end: Option<usize>,
+}
+
+impl Frame {
+    #[must_use]
+    pub fn new(values: Vec<i64>) -> Self {
+        let sorted = vec![false; values.len()];
+        Frame { values, sorted, ..Frame::default() }
+    }
+
+    pub fn apply(&mut self, operation: &Operation) {
+        match *operation {
+            Operation::Compare { i, j } => self.comparing = Some((i, j)),
+            Operation::Swap { i, j } => self.values.swap(i, j),
+            Operation::Write { index, value } => self.values[index] = value,
+            Operation::MarkSorted { index } => self.sorted[index] = true,
+            Operation::EnterRange { start, end } => self.ranges.push((start, end)),
+            Operation::LeaveRange { .. } => {
+                self.ranges.pop();
+            }
+            Operation::Phase { .. } => {}
+            Operation::HeapBoundary { end } => self.heap_end = Some(end),
+        }
+    }
+
+    #[must_use]
+    pub fn is_comparing(&self, index: usize) -> bool {
+        self.comparing.is_some_and(|(i, j)| index == i || index == j)
+    }
+
+    #[must_use]
+    pub fn is_sorted(&self, index: usize) -> bool {
+        self.sorted.get(index).copied().unwrap_or(false)
+    }
+
+    #[must_use]
+    pub fn innermost_range(&self) -> Option<(usize, usize)> {
+        self.ranges.last().copied()
+    }
+
+    /// The pivot quick sort is partitioning around: the last element of the
+    /// range it is working in. Other algorithms have none.
+    #[must_use]
+    pub fn pivot(&self, algorithm: AlgorithmType) -> Option<usize> {
+        if algorithm != AlgorithmType::Quick {
+            return None;
+        }
+        self.innermost_range().map(|(_, end)| end - 1)
+    }
+
+    /// Drops the highlights, e.g. once a run finished or was stopped.
+    pub fn clear_highlights(&mut self) {
+        self.comparing = None;
+        self.ranges.clear();
+        self.heap_end = None;
+    }
+
+    /// The frame after the first `steps` operations of `operations`.
+    #[must_use]
+    pub fn at(values: Vec<i64>, operations: &[Operation], steps: usize) -> Self {
+        let mut frame = Frame::new(values);
+        for operation in &operations[..steps.min(operations.len())] {
+            frame.apply(operation);
+        }
+        frame
+    }
+}
+
+/// Whether the operation changes what is on screen enough to pause after it.
+#[must_use]
+pub fn is_visible(operation: &Operation) -> bool {
+    matches!(operation, Operation::Compare { .. } | Operation::Swap { .. } | Operation::Write { .. })
+}
+
+/// A recorded run that can be stepped through in both directions, one
+/// visible operation per step.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Replay {
+    input: Vec<i64>,
+    operations: Vec<Operation>,
+    /// Operation counts after which a step ends: one per visible operation,
+    /// so a step never only enters a range or marks a bar.
+    stops: Vec<usize>,
+    step: usize,
+    frame: Frame,
+}
+
+impl Replay {
+    #[must_use]
+    pub fn new(input: Vec<i64>, operations: Vec<Operation>) -> Self {
+        let mut stops: Vec<usize> = operations.iter().enumerate().filter(|(_, operation)| is_visible(operation)).map(|(index, _)| index + 1).collect();
+        if stops.last() != Some(&operations.len()) {
+            stops.push(operations.len());
+        }
+        let frame = Frame::new(input.clone());
+        Replay { input, operations, stops, step: 0, frame }
+    }
+
+    /// Number of steps in the whole run; step 0 is the input.
+    #[must_use]
+    pub fn steps(&self) -> usize {
+        self.stops.len()
+    }
+
+    #[must_use]
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    #[must_use]
+    pub fn frame(&self) -> &Frame {
+        &self.frame
+    }
+
+    #[must_use]
+    pub fn input(&self) -> &[i64] {
+        &self.input
+    }
+
+    #[must_use]
+    pub fn operations(&self) -> &[Operation] {
+        &self.operations
+    }
+
+    /// Number of operations behind the current step.
+    #[must_use]
+    pub fn applied(&self) -> usize {
+        self.applied_at(self.step)
+    }
+
+    /// Moves to `step`, at most the last one. Moving forward applies the
+    /// operations in between; moving back replays the trace from the start
+    /// since operations cannot be undone. The last step drops the highlights.
+    pub fn seek(&mut self, step: usize) {
+        let target = step.min(self.steps());
+        if target >= self.step {
+            for operation in &self.operations[self.applied()..self.applied_at(target)] {
+                self.frame.apply(operation);
+            }
+        } else {
+            self.frame = Frame::at(self.input.clone(), &self.operations, self.applied_at(target));
+        }
+        self.step = target;
+        if target == self.steps() && target > 0 {
+            self.frame.clear_highlights();
+        }
+    }
+
+    fn applied_at(&self, step: usize) -> usize {
+        step.checked_sub(1).map_or(0, |step| self.stops[step])
+    }
+}
@@ -0,0 +1,99 @@
+//! Still SVG images of a single step, in the colours the app uses, for
+//! documents and for comparing rendered frames against golden files.
+
+use std::fmt::Write;
+
+use crate::animation::{hex, Layout, BACKGROUND};
+use crate::pathfinding::{Cell, Grid, PathResult};
+use crate::steps::BarColor;
+
+/// Side of one grid cell, as in the pathfinding page.
+pub const CELL_SIZE: u16 = 20;
+
+const WALL: &str = "black";
+const VISITED: &str = "lightblue";
+const PATH: &str = "yellow";
+const START: &str = "green";
+const END: &str = "red";
+const EMPTY: &str = "white";
+const CELL_BORDER: &str = "#ccc";
+
+/// The bars of one step, laid out as in the animations.
+#[must_use]
+pub fn bars_svg(values: &[f64], colors: &[BarColor], width: u16, height: u16) -> String {
+    let layout = Layout::new(values, values.len(), width, height);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">\n<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n",
+        hex(BACKGROUND),
+    );
+    for (index, (&value, &color)) in values.iter().zip(colors).enumerate() {
+        let (x, bar_width) = layout.column(index);
+        let (y, bar_height) = layout.bar(value);
+        let _ = writeln!(
+            svg,
+            "<rect x=\"{x}\" y=\"{y}\" width=\"{bar_width}\" height=\"{bar_height}\" fill=\"{}\"/>",
+            hex(color.rgb()),
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Number of steps a search has: one per expanded cell, then one per cell
+/// of the path.
+#[must_use]
+pub fn path_steps(result: &PathResult) -> usize {
+    result.visited.len() + result.path.len()
+}
+
+/// The grid after `step` steps of the search, one square per cell. Weights
+/// above 1 are written into their cells.
+#[must_use]
+pub fn grid_svg(grid: &Grid, start: Cell, end: Cell, result: &PathResult, step: usize) -> String {
+    let expanded = step.min(result.visited.len());
+    let on_path = (step - expanded).min(result.path.len());
+    let mut fills = vec![EMPTY; grid.rows() * grid.cols()];
+    for &(row, col) in &result.visited[..expanded] {
+        fills[row * grid.cols() + col] = VISITED;
+    }
+    for &(row, col) in &result.path[..on_path] {
+        fills[row * grid.cols() + col] = PATH;
+    }
+
+    let size = usize::from(CELL_SIZE);
+    let (width, height) = (grid.cols() * size, grid.rows() * size);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">\n<g stroke=\"{CELL_BORDER}\">\n",
+    );
+    let mut weights = String::new();
+    for row in 0..grid.rows() {
+        for col in 0..grid.cols() {
+            let cell = (row, col);
+            let fill = if cell == start {
+                START
+            } else if cell == end {
+                END
+            } else if grid.is_wall(cell) {
+                WALL
+            } else {
+                fills[row * grid.cols() + col]
+            };
+            let (x, y) = (col * size, row * size);
+            let _ = writeln!(svg, "<rect x=\"{x}\" y=\"{y}\" width=\"{size}\" height=\"{size}\" fill=\"{fill}\"/>");
+            let weight = grid.weight(cell);
+            if weight > 1 && !grid.is_wall(cell) {
+                let _ = writeln!(weights, "<text x=\"{}\" y=\"{}\">{weight}</text>", x + size / 2, y + size / 2);
+            }
+        }
+    }
+    svg.push_str("</g>\n");
+    if !weights.is_empty() {
+        let _ = write!(
+            svg,
+            "<g font-family=\"sans-serif\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\">\n{weights}</g>\n",
+            size / 2,
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
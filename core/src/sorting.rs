@@ -0,0 +1,310 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+use crate::trace::{Metrics, Phase, Recorder, TracedArray};
+
+#[derive(EnumIter, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "lowercase"))]
+pub enum AlgorithmType {
+    Selection,
+    Bubble,
+    Insertion,
+    Merge,
+    Quick,
+    Heap,
+}
+
+impl AlgorithmType {
+    /// The lowercase name used in routes and on the command line, e.g. `quick`.
+    #[must_use]
+    pub fn slug(self) -> &'static str {
+        match self {
+            AlgorithmType::Selection => "selection",
+            AlgorithmType::Bubble => "bubble",
+            AlgorithmType::Insertion => "insertion",
+            AlgorithmType::Merge => "merge",
+            AlgorithmType::Quick => "quick",
+            AlgorithmType::Heap => "heap",
+        }
+    }
+}
+
+impl FromStr for AlgorithmType {
+    type Err = &'static str;
+
+    fn from_str(slug: &str) -> Result<Self, Self::Err> {
+        match slug {
+            "selection" => Ok(AlgorithmType::Selection),
+            "bubble" => Ok(AlgorithmType::Bubble),
+            "insertion" => Ok(AlgorithmType::Insertion),
+            "merge" => Ok(AlgorithmType::Merge),
+            "quick" => Ok(AlgorithmType::Quick),
+            "heap" => Ok(AlgorithmType::Heap),
+            _ => Err("Invalid algorithm type."),
+        }
+    }
+}
+
+impl fmt::Display for AlgorithmType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.slug())
+    }
+}
+
+/// The sorted array together with everything recorded on the way there.
+#[derive(Debug, Clone)]
+pub struct Sorted<R> {
+    pub result: Vec<i64>,
+    pub operations: R,
+    pub metrics: Metrics,
+}
+
+/// Runs `algorithm` over `values`, feeding every operation into `recorder`
+/// as it happens.
+pub fn sort<R: Recorder>(algorithm: AlgorithmType, values: Vec<i64>, recorder: R) -> Sorted<R> {
+    let mut array = TracedArray::new(values, recorder);
+    match algorithm {
+        AlgorithmType::Selection => selection_sort(&mut array),
+        AlgorithmType::Bubble => bubble_sort(&mut array),
+        AlgorithmType::Insertion => insertion_sort(&mut array),
+        AlgorithmType::Merge => merge_sort(&mut array),
+        AlgorithmType::Quick => quick_sort(&mut array),
+        AlgorithmType::Heap => heap_sort(&mut array),
+    }
+    let (result, operations, metrics) = array.finish();
+    Sorted { result, operations, metrics }
+}
+
+fn selection_sort<R: Recorder>(array: &mut TracedArray<R>) {
+    for i in 0..array.len() {
+        let mut min_index = i;
+        for j in i+1..array.len() {
+            if array.compare(j, min_index) == Ordering::Less {
+                min_index = j;
+            }
+        }
+        if min_index != i {
+            array.swap(i, min_index);
+        }
+        array.mark_sorted(i);
+    }
+}
+
+fn bubble_sort<R: Recorder>(array: &mut TracedArray<R>) {
+    let len = array.len();
+    for i in 0..len {
+        let mut swapped = false;
+        for j in 0..len-i-1 {
+            if array.compare(j, j+1) == Ordering::Greater {
+                array.swap(j, j+1);
+                swapped = true;
+            }
+        }
+        array.mark_sorted(len-i-1);
+        // a pass without swaps means the rest is already in order
+        if !swapped {
+            break;
+        }
+    }
+}
+
+fn insertion_sort<R: Recorder>(array: &mut TracedArray<R>) {
+    for i in 1..array.len() {
+        let key = array.read(i);
+        let mut j = i;
+        // `j` is the hole the key will be written into; the key is compared as if it sat there
+        while j > 0 {
+            let previous = array.read(j-1);
+            if array.compare_values(j-1, j, previous, key) != Ordering::Greater {
+                break;
+            }
+            array.write(j, previous);
+            j -= 1;
+        }
+        if j != i {
+            array.write(j, key);
+        }
+    }
+}
+
+fn merge_sort<R: Recorder>(array: &mut TracedArray<R>) {
+    let end = array.len();
+    merge_sort_helper(array, 0, end);
+}
+
+fn merge_sort_helper<R: Recorder>(array: &mut TracedArray<R>, start: usize, end: usize) {
+    if end - start > 1 {
+        array.enter_range(start, end);
+        let mid = start + (end - start) / 2;
+        merge_sort_helper(array, start, mid);
+        merge_sort_helper(array, mid, end);
+        merge(array, start, mid, end);
+        array.leave_range(start, end);
+    }
+}
+
+fn merge<R: Recorder>(array: &mut TracedArray<R>, start: usize, mid: usize, end: usize) {
+    let left = array.copy_out(start, mid);
+    let right = array.copy_out(mid, end);
+    let (mut i, mut j) = (0, 0);
+    for k in start..end {
+        let take_left = if i < left.len() && j < right.len() {
+            // Both runs now live in the auxiliary buffers, so the comparison
+            // is reported against the positions they were copied from.
+            array.compare_values(start + i, mid + j, left[i], right[j]) != Ordering::Greater
+        } else {
+            i < left.len()
+        };
+        if take_left {
+            array.write(k, left[i]);
+            i += 1;
+        } else {
+            array.write(k, right[j]);
+            j += 1;
+        }
+    }
+    array.release(&left);
+    array.release(&right);
+}
+
+fn quick_sort<R: Recorder>(array: &mut TracedArray<R>) {
+    if let Some(high) = array.len().checked_sub(1) {
+        quick_sort_helper(array, 0, high);
+    }
+}
+
+fn quick_sort_helper<R: Recorder>(array: &mut TracedArray<R>, mut low: usize, mut high: usize) {
+    // recurses into the smaller side and carries on with the larger one, so
+    // already sorted input cannot nest a call per element
+    while low < high {
+        array.enter_range(low, high + 1);
+        let pi = partition(array, low, high);
+        array.mark_sorted(pi);
+        array.leave_range(low, high + 1);
+        if pi - low < high - pi {
+            if pi > low {
+                quick_sort_helper(array, low, pi - 1);
+            }
+            low = pi + 1;
+        } else {
+            quick_sort_helper(array, pi + 1, high);
+            if pi == low {
+                return;
+            }
+            high = pi - 1;
+        }
+    }
+    if low == high {
+        array.mark_sorted(low);
+    }
+}
+
+fn partition<R: Recorder>(array: &mut TracedArray<R>, low: usize, high: usize) -> usize {
+    let mut i = low;
+    for j in low..high {
+        if array.compare(j, high) == Ordering::Less {
+            if i != j {
+                array.swap(i, j);
+            }
+            i += 1;
+        }
+    }
+    if i != high {
+        array.swap(i, high);
+    }
+    i
+}
+
+fn heap_sort<R: Recorder>(array: &mut TracedArray<R>) {
+    let end = array.len();
+    array.enter_phase(Phase::BuildHeap);
+    array.heap_boundary(end);
+    for i in (0..end / 2).rev() {
+        heapify(array, end, i);
+    }
+    array.enter_phase(Phase::ExtractMax);
+    for last in (1..end).rev() {
+        // move the current maximum behind the heap, then restore the heap on what is left
+        array.swap(0, last);
+        array.mark_sorted(last);
+        array.heap_boundary(last);
+        heapify(array, last, 0);
+    }
+}
+
+/// Sifts the element at `i` down until it is no smaller than its children.
+/// Loops rather than recurses so the sort needs no extra memory.
+fn heapify<R: Recorder>(array: &mut TracedArray<R>, end: usize, mut i: usize) {
+    loop {
+        let mut largest = i;
+        let left = 2 * i + 1;
+        let right = 2 * i + 2;
+        if left < end && array.compare(left, largest) == Ordering::Greater {
+            largest = left;
+        }
+        if right < end && array.compare(right, largest) == Ordering::Greater {
+            largest = right;
+        }
+        if largest == i {
+            return;
+        }
+        array.swap(i, largest);
+        i = largest;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use strum::IntoEnumIterator;
+
+    use super::*;
+    use crate::trace::Discard;
+
+    /// Deterministic values that spread over the whole `i64` range.
+    fn scrambled(length: usize) -> Vec<i64> {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        (0..length)
+            .map(|_| {
+                state = state.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+                i64::from_ne_bytes(state.to_ne_bytes())
+            })
+            .collect()
+    }
+
+    fn assert_sorts(algorithm: AlgorithmType, values: Vec<i64>) {
+        let mut expected = values.clone();
+        expected.sort_unstable();
+        assert_eq!(sort(algorithm, values, Discard).result, expected, "{algorithm:?} sort");
+    }
+
+    #[test]
+    fn every_sort_sorts() {
+        let inputs = [
+            Vec::new(),
+            vec![7],
+            vec![2, 1],
+            vec![3, 1, 3, 2, 1, 3],
+            (0..50).collect(),
+            (0..50).rev().collect(),
+            vec![4; 20],
+            vec![i64::MAX, 0, i64::MIN, -1, 1, i64::MIN],
+            scrambled(500),
+            scrambled(500).into_iter().map(|value| value % 10).collect(),
+        ];
+        for algorithm in AlgorithmType::iter() {
+            for values in &inputs {
+                assert_sorts(algorithm, values.clone());
+            }
+        }
+    }
+
+    #[test]
+    fn quick_sort_survives_long_sorted_input() {
+        // one level per element would overflow the test thread's stack
+        assert_sorts(AlgorithmType::Quick, (0..100_000).collect());
+    }
+}
@@ -0,0 +1,144 @@
+//! The chart states of a sorting run, one per visible operation, as the
+//! frontend chart and the exported animations show them.
+
+use strum_macros::Display;
+
+use crate::narration;
+use crate::pseudocode;
+use crate::replay::{is_visible, Frame};
+use crate::sorting::{self, AlgorithmType};
+use crate::trace::Operation;
+
+#[derive(Debug, Clone, Copy, PartialEq, Display)]
+pub enum BarColor {
+    Green,
+    Grey,
+    Orange,
+    Red,
+}
+
+impl BarColor {
+    /// The colour the chart draws bars in.
+    #[must_use]
+    pub fn rgb(self) -> (u8, u8, u8) {
+        match self {
+            BarColor::Green => (0, 255, 0),
+            BarColor::Grey => (108, 108, 108),
+            BarColor::Orange => (227, 150, 62),
+            BarColor::Red => (239, 68, 68),
+        }
+    }
+}
+
+/// The bar heights and colours of every step, from the input to the sorted
+/// array.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Steps {
+    pub steps: Vec<Vec<f64>>,
+    pub palette: Vec<Vec<BarColor>>,
+    /// The pseudocode line each step carries out; `None` for the input and
+    /// the finished array.
+    pub lines: Vec<Option<usize>>,
+    /// What each step does, in words.
+    pub narration: Vec<String>,
+}
+
+impl Steps {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sorts `values` with `algorithm` and keeps one step per operation that
+    /// changes the chart.
+    #[must_use]
+    pub fn record(algorithm: AlgorithmType, values: Vec<i64>) -> Self {
+        let operations = sorting::sort(algorithm, values.clone(), Vec::new()).operations;
+        Steps::from_trace(algorithm, values, &operations)
+    }
+
+    /// Replays a trace `algorithm` recorded on `values`, keeping one step per
+    /// visible operation plus the input and the finished array.
+    #[must_use]
+    pub fn from_trace(algorithm: AlgorithmType, values: Vec<i64>, operations: &[Operation]) -> Self {
+        let mut steps = Steps::new();
+        let mut notes = narration::narrate_steps(algorithm, values.clone(), operations).into_iter();
+        let mut frame = Frame::new(values);
+        steps.push_frame(algorithm, &frame, None, notes.next().unwrap_or_default());
+        for (operation, line) in operations.iter().zip(pseudocode::lines(algorithm, operations)) {
+            frame.apply(operation);
+            if is_visible(operation) {
+                steps.push_frame(algorithm, &frame, line, notes.next().unwrap_or_default());
+            }
+        }
+        frame.clear_highlights();
+        steps.push_frame(algorithm, &frame, None, notes.next().unwrap_or_default());
+        steps
+    }
+
+    /// Number of steps [`Steps::from_trace`] makes of `operations`, without
+    /// building them.
+    #[must_use]
+    pub fn count(operations: &[Operation]) -> usize {
+        operations.iter().filter(|operation| is_visible(operation)).count() + 2
+    }
+
+    /// Step `step` of what [`Steps::from_trace`] would record, as bar heights
+    /// and colours, without building the others. `None` past the last step.
+    #[must_use]
+    pub fn step(algorithm: AlgorithmType, values: Vec<i64>, operations: &[Operation], step: usize) -> Option<(Vec<f64>, Vec<BarColor>)> {
+        let applied = match step.checked_sub(1) {
+            None => 0,
+            Some(visible) => match operations.iter().enumerate().filter(|(_, operation)| is_visible(operation)).nth(visible) {
+                Some((index, _)) => index + 1,
+                None if step == Steps::count(operations) - 1 => operations.len(),
+                None => return None,
+            },
+        };
+        let mut frame = Frame::at(values, operations, applied);
+        if step == Steps::count(operations) - 1 {
+            frame.clear_highlights();
+        }
+        Some((bars(&frame), palette(algorithm, &frame)))
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    fn push_frame(&mut self, algorithm: AlgorithmType, frame: &Frame, line: Option<usize>, note: String) {
+        self.steps.push(bars(frame));
+        self.palette.push(palette(algorithm, frame));
+        self.lines.push(line);
+        self.narration.push(note);
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn bars(frame: &Frame) -> Vec<f64> {
+    frame.values.iter().map(|&value| value as f64).collect()
+}
+
+/// The pivot stays red even while it is compared, as in the quick sort page.
+fn palette(algorithm: AlgorithmType, frame: &Frame) -> Vec<BarColor> {
+    let pivot = frame.pivot(algorithm);
+    (0..frame.values.len())
+        .map(|index| {
+            if pivot == Some(index) {
+                BarColor::Red
+            } else if frame.is_comparing(index) {
+                BarColor::Green
+            } else if frame.is_sorted(index) {
+                BarColor::Orange
+            } else {
+                BarColor::Grey
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,171 @@
+use std::cmp::Ordering;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A single primitive step performed by a sorting algorithm.
+///
+/// Ranges are half-open: `start` is inclusive, `end` is exclusive.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(tag = "op", rename_all = "snake_case"))]
+pub enum Operation {
+    Compare { i: usize, j: usize },
+    Swap { i: usize, j: usize },
+    Write { index: usize, value: i64 },
+    MarkSorted { index: usize },
+    EnterRange { start: usize, end: usize },
+    LeaveRange { start: usize, end: usize },
+    Phase { phase: Phase },
+    /// The heap now occupies `0..end`; everything past it is sorted.
+    HeapBoundary { end: usize },
+}
+
+/// Labelled stage of an algorithm that runs in distinct passes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
+pub enum Phase {
+    BuildHeap,
+    ExtractMax,
+}
+
+/// Destination for the operations a sort performs.
+pub trait Recorder {
+    fn record(&mut self, operation: Operation);
+}
+
+impl Recorder for Vec<Operation> {
+    fn record(&mut self, operation: Operation) {
+        self.push(operation);
+    }
+}
+
+impl<R: Recorder + ?Sized> Recorder for &mut R {
+    fn record(&mut self, operation: Operation) {
+        (**self).record(operation);
+    }
+}
+
+/// Throws every operation away, for runs where only the result and timing matter.
+pub struct Discard;
+
+impl Recorder for Discard {
+    #[inline]
+    fn record(&mut self, _operation: Operation) {}
+}
+
+/// Work counted while sorting. Every algorithm goes through the same
+/// `TracedArray` methods, so the numbers are comparable across algorithms.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Metrics {
+    pub comparisons: u64,
+    pub swaps: u64,
+    pub reads: u64,
+    pub writes: u64,
+    /// Peak number of elements held outside the array at once.
+    pub auxiliary_memory: usize,
+}
+
+/// Array wrapper that records and counts every operation performed through it.
+pub struct TracedArray<R: Recorder = Vec<Operation>> {
+    values: Vec<i64>,
+    sorted: Vec<bool>,
+    recorder: R,
+    metrics: Metrics,
+    auxiliary_in_use: usize,
+}
+
+impl<R: Recorder> TracedArray<R> {
+    pub fn new(values: Vec<i64>, recorder: R) -> Self {
+        let sorted = vec![false; values.len()];
+        TracedArray { values, sorted, recorder, metrics: Metrics::default(), auxiliary_in_use: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn read(&mut self, index: usize) -> i64 {
+        self.metrics.reads += 1;
+        self.values[index]
+    }
+
+    /// Copies `start..end` into a new auxiliary buffer, which counts towards
+    /// `auxiliary_memory` until handed back with `release`.
+    pub fn copy_out(&mut self, start: usize, end: usize) -> Vec<i64> {
+        self.metrics.reads += (end - start) as u64;
+        self.auxiliary_in_use += end - start;
+        self.metrics.auxiliary_memory = self.metrics.auxiliary_memory.max(self.auxiliary_in_use);
+        self.values[start..end].to_vec()
+    }
+
+    pub fn release(&mut self, buffer: &[i64]) {
+        self.auxiliary_in_use -= buffer.len();
+    }
+
+    pub fn record(&mut self, operation: Operation) {
+        self.recorder.record(operation);
+    }
+
+    pub fn compare(&mut self, i: usize, j: usize) -> Ordering {
+        self.metrics.reads += 2;
+        self.compare_values(i, j, self.values[i], self.values[j])
+    }
+
+    /// Compares values the caller already holds, reporting them as the
+    /// elements at `i` and `j`.
+    pub fn compare_values(&mut self, i: usize, j: usize, left: i64, right: i64) -> Ordering {
+        self.metrics.comparisons += 1;
+        self.record(Operation::Compare { i, j });
+        left.cmp(&right)
+    }
+
+    pub fn swap(&mut self, i: usize, j: usize) {
+        self.metrics.swaps += 1;
+        self.metrics.reads += 2;
+        self.metrics.writes += 2;
+        self.record(Operation::Swap { i, j });
+        self.values.swap(i, j);
+    }
+
+    pub fn write(&mut self, index: usize, value: i64) {
+        self.metrics.writes += 1;
+        self.record(Operation::Write { index, value });
+        self.values[index] = value;
+    }
+
+    pub fn mark_sorted(&mut self, index: usize) {
+        if !self.sorted[index] {
+            self.sorted[index] = true;
+            self.record(Operation::MarkSorted { index });
+        }
+    }
+
+    pub fn enter_range(&mut self, start: usize, end: usize) {
+        self.record(Operation::EnterRange { start, end });
+    }
+
+    pub fn leave_range(&mut self, start: usize, end: usize) {
+        self.record(Operation::LeaveRange { start, end });
+    }
+
+    pub fn enter_phase(&mut self, phase: Phase) {
+        self.record(Operation::Phase { phase });
+    }
+
+    pub fn heap_boundary(&mut self, end: usize) {
+        self.record(Operation::HeapBoundary { end });
+    }
+
+    /// Marks every index that is not yet in its final position as sorted and
+    /// returns the final array together with the recorder and the counted work.
+    pub fn finish(mut self) -> (Vec<i64>, R, Metrics) {
+        for index in 0..self.values.len() {
+            self.mark_sorted(index);
+        }
+        (self.values, self.recorder, self.metrics)
+    }
+}
@@ -1,69 +1,66 @@
 use leptos::*;
 use rand::seq::SliceRandom;
-use rand::{thread_rng, Rng};
-use std::time::Duration;
+use rand::Rng;
+use crate::narration::Narration;
 use crate::navbar::NavBar;
+use crate::player::{Playback, Player};
+use crate::seed::{random_seed, seeded_rng};
+use crate::share::{share_sort, SortLink};
+use crate::shortcuts::{use_shortcuts, Shortcut};
 use crate::sorting_controls::SortingControls;
 use crate::sorting_info::{AlgorithmInfoPanel, BOGO_SORT_INFO};
 
+/// Attempts after which the shuffling gives up.
+const MAX_ATTEMPTS: usize = 10000;
+
 #[component]
 pub fn BogoSortVisualizer() -> impl IntoView {
-    let (array, set_array) = create_signal(vec![]);
-    let (sorting, set_sorting) = create_signal(false);
-    let (array_size, set_array_size) = create_signal(4);
-    let (attempts, set_attempts) = create_signal(0);
+    // the generated array followed by every shuffle of it, one per step
+    let shuffles = store_value(Vec::<Vec<i32>>::new());
+    let link = SortLink::read();
+    let playback = Playback::new(link.speed.unwrap_or(10.0));
+    let (array_size, set_array_size) = create_signal(link.size.unwrap_or(4));
+    let (seed, set_seed) = create_signal(link.seed.unwrap_or_else(random_seed));
+    let array = create_memo(move |_| {
+        let step = playback.step.get();
+        shuffles.with_value(|shuffles| shuffles.get(step).cloned().unwrap_or_default())
+    });
+    let attempts = move || playback.step.get();
+    let line: Signal<Option<usize>> = Signal::derive(move || playback.playing.get().then_some(1));
+    // one note per shuffle, written when the shuffles are
+    let notes = store_value(Vec::<String>::new());
+    let narration = Signal::derive(move || {
+        let step = playback.step.get();
+        // nothing has run since the array was generated or reset
+        if playback.last.get() == 0 {
+            return Vec::new();
+        }
+        notes.with_value(|notes| notes[..(step + 1).min(notes.len())].to_vec())
+    });
     
-    let generate_array = move |size: usize| {
-        let mut rng = thread_rng();
+    let generate_array = move |size: usize, seed: u64| {
+        let mut rng = seeded_rng(seed);
         let new_array: Vec<i32> = (0..size)
             .map(|_| rng.gen_range(10..100))
             .collect();
-        set_array(new_array);
-        set_attempts(0);
-    };
-
-    fn is_sorted(arr: &[i32]) -> bool {
-        arr.windows(2).all(|w| w[0] <= w[1])
-    }
-
-    let bogo_sort = move |_: web_sys::MouseEvent| {
-        set_sorting.set(true);
-        set_attempts(0);
-        
-        create_local_resource(
-            move || (),
-            move |_| async move {
-                let mut current_array = array.get();
-                let mut rng = thread_rng();
-                let mut attempt_count = 0;
-
-                while !is_sorted(&current_array) && sorting.get() {
-                    current_array.shuffle(&mut rng);
-                    set_array(current_array.clone());
-                    attempt_count += 1;
-                    set_attempts(attempt_count);
-                    
-                    // Add delay to visualize the shuffling
-                    delay(100).await;
-                    
-                    // Safety check to prevent infinite loops
-                    if attempt_count > 10000 {
-                        set_sorting.set(false);
-                        break;
-                    }
-                }
-                
-                if !sorting.get() {
-                    set_attempts(0);
-                }
-                
-                set_sorting.set(false);
-            },
-        );
+        shuffles.set_value(vec![new_array]);
+        playback.load(0);
     };
 
-    let stop_sorting = move |_: web_sys::MouseEvent| {
-        set_sorting.set(false);
+    // shuffles up front with the same seed, so every attempt can be stepped back to
+    let bogo_sort = move || {
+        let mut rng = seeded_rng(seed.get_untracked());
+        shuffles.update_value(|shuffles| {
+            shuffles.truncate(1);
+            let mut current_array = shuffles[0].clone();
+            while !is_sorted(&current_array) && shuffles.len() <= MAX_ATTEMPTS {
+                current_array.shuffle(&mut rng);
+                shuffles.push(current_array.clone());
+            }
+        });
+        notes.set_value(shuffles.with_value(|shuffles| narrate_shuffles(shuffles)));
+        playback.load(shuffles.with_value(Vec::len) - 1);
+        playback.play();
     };
 
     let on_size_change = move |ev| {
@@ -72,32 +69,40 @@ pub fn BogoSortVisualizer() -> impl IntoView {
             .unwrap_or(10)
             .min(15);
         set_array_size(new_size);
-        generate_array(new_size);
     };
 
-    create_effect(move |_| {
-        generate_array(array_size());
+    let show_array = move |values: Vec<i64>| {
+        shuffles.set_value(vec![values.into_iter().filter_map(|value| i32::try_from(value).ok()).collect()]);
+        playback.load(0);
+    };
+    share_sort(link, playback, array_size, seed, generate_array, show_array, bogo_sort);
+
+    use_shortcuts(move |shortcut| match shortcut {
+        Shortcut::TogglePlay if playback.last.get_untracked() == 0 => bogo_sort(),
+        Shortcut::Generate if !playback.playing.get_untracked() => set_seed(random_seed()),
+        Shortcut::Reset => {
+            shuffles.update_value(|shuffles| shuffles.truncate(1));
+            playback.load(0);
+        }
+        _ => {
+            playback.handle(shortcut);
+        }
     });
 
     view! {
         <NavBar/>
         <SortingControls
             array_size=array_size
-            is_sorting=sorting
-            on_generate=move |_| generate_array(array_size())
-            on_sort=bogo_sort
+            is_sorting=playback.playing
+            on_generate=move |_| set_seed(random_seed())
+            seed=seed
+            on_seed_change=move |value| set_seed(value)
+            on_sort=move |_| bogo_sort()
             on_size_change=on_size_change
             min_size=3
             max_size=15
-        >
-            <button
-                class="px-4 py-2 bg-red-500 text-white rounded hover:bg-red-600 transition-colors"
-                on:click=stop_sorting
-                disabled=move || !sorting.get()
-            >
-                "Stop Sorting"
-            </button>
-        </SortingControls>
+        />
+        <Player playback=playback/>
         
         <div class="w-full max-w-5xl mx-auto px-4">
             <div class="mb-4">
@@ -110,7 +115,7 @@ pub fn BogoSortVisualizer() -> impl IntoView {
                 {move || {
                     array.get().into_iter().enumerate().map(|(_, value)| {
                         let height = format!("{}%", value);
-                        let is_sorting = sorting();
+                        let is_sorting = playback.playing.get();
                         
                         let color = if is_sorting {
                             "#ef4444"
@@ -135,16 +140,30 @@ pub fn BogoSortVisualizer() -> impl IntoView {
                 }}
             </div>
         </div>
-        <AlgorithmInfoPanel algorithm_info=BOGO_SORT_INFO/>
+        <Narration history=narration/>
+        <AlgorithmInfoPanel algorithm_info=BOGO_SORT_INFO active_line=line/>
     }
 }
 
-fn delay(ms: u64) -> impl std::future::Future<Output = ()> {
-        async move {
-            let (tx, rx) = futures::channel::oneshot::channel::<()>();
-            set_timeout(move || {
-                let _ = tx.send(());
-            }, Duration::from_millis(ms));
-            let _ = rx.await;
+fn is_sorted(arr: &[i32]) -> bool {
+    arr.windows(2).all(|w| w[0] <= w[1])
+}
+
+/// What each step of a run does: the input, then every shuffle of it.
+fn narrate_shuffles(shuffles: &[Vec<i32>]) -> Vec<String> {
+    let Some((input, attempts)) = shuffles.split_first() else {
+        return Vec::new();
+    };
+    let mut notes = vec![format!("Starting bogo sort on {} values: {input:?}.", input.len())];
+    notes.extend(attempts.iter().enumerate().map(|(index, values)| {
+        let attempt = index + 1;
+        if is_sorted(values) {
+            format!("Shuffle {attempt}: {values:?} is sorted, so the run is over.")
+        } else if attempt == attempts.len() {
+            format!("Shuffle {attempt}: {values:?} is still not sorted; giving up.")
+        } else {
+            format!("Shuffle {attempt}: {values:?} is still not sorted, so shuffle again.")
         }
-    }
\ No newline at end of file
+    }));
+    notes
+}
@@ -1,127 +1,73 @@
+use algoviz_core::sorting::AlgorithmType;
 use leptos::*;
 use rand::Rng;
-use std::time::Duration;
+use crate::download::DownloadAnimation;
+use crate::narration::Narration;
 use crate::navbar::NavBar;
+use crate::player::Player;
+use crate::replay::SortRun;
+use crate::seed::{random_seed, seeded_rng};
+use crate::share::{share_sort, SortLink};
+use crate::shortcuts::{use_shortcuts, Shortcut};
 use crate::sorting_controls::SortingControls;
-use crate::sorting_info::{AlgorithmInfoPanel, BUBBLE_SORT_INFO};
+use crate::sorting_info::AlgorithmInfoPanel;
 
 #[component]
 pub fn BubbleSortVisualizer() -> impl IntoView {
-    let (array, set_array) = create_signal(vec![]);
-    let (sorting, set_sorting) = create_signal(false);
-    let (comparison_idx, set_comparison_idx) = create_signal(None::<(usize, usize)>);
-    let (array_size, set_array_size) = create_signal(15);
-    let (sorted_indices, set_sorted_indices) = create_signal(Vec::new());
+    let link = SortLink::read();
+    let run = SortRun::new(AlgorithmType::Bubble, link.speed.unwrap_or(20.0));
+    let SortRun { frame, line, playback, narration, .. } = run;
+    let (array_size, set_array_size) = create_signal(link.size.unwrap_or(15));
+    let (seed, set_seed) = create_signal(link.seed.unwrap_or_else(random_seed));
     
-    let generate_array = move |size: usize| {
-        let mut rng = rand::thread_rng();
-        let new_array: Vec<i32> = (0..size)
+    let generate_array = move |size: usize, seed: u64| {
+        let mut rng = seeded_rng(seed);
+        let new_array: Vec<i64> = (0..size)
             .map(|_| rng.gen_range(10..100))
             .collect();
-        set_array(new_array);
-        set_comparison_idx(None);
-        set_sorted_indices(Vec::new());
+        run.show(new_array);
     };
 
-    let bubble_sort = move |_| {
-        set_sorting.set(true);
-        set_sorted_indices(Vec::new());
-        
-        create_local_resource(
-            move || (),
-            move |_| async move {
-                let mut current_array = array.get();
-                let len = current_array.len();
-                let mut sorted = Vec::new();
-
-                'outer: for i in 0..len {
-                    let mut swapped = false;
-                    for j in 0..len - i - 1 {
-                        // Check if sorting has been stopped
-                        if !sorting.get() {
-                            set_comparison_idx.set(None);
-                            set_sorted_indices(Vec::new());
-                            break 'outer;
-                        }
-
-                        set_comparison_idx.set(Some((j, j + 1)));
-                        
-                        if current_array[j] > current_array[j + 1] {
-                            current_array.swap(j, j + 1);
-                            set_array.set(current_array.clone());
-                            swapped = true;
-                        }
-                        
-                        delay(50).await;
-                    }
-                    
-                    if sorting.get() {
-                        sorted.push(len - i - 1);
-                        set_sorted_indices(sorted.clone());
-                        
-                        if !swapped {
-                            // If no swapping occurred, all remaining elements are sorted
-                            for k in 0..len-i-1 {
-                                sorted.push(k);
-                            }
-                            set_sorted_indices(sorted);
-                            break;
-                        }
-                    }
-                }
-                
-                if sorting.get() {
-                    set_sorted_indices((0..len).collect());
-                }
-                
-                set_sorting.set(false);
-                set_comparison_idx.set(None);
-            },
-        );
-    };
+    let bubble_sort = move |_: web_sys::MouseEvent| run.start();
 
     let on_size_change = move |ev| {
         let new_size = event_target_value(&ev)
             .parse::<usize>()
             .unwrap_or(50);
         set_array_size(new_size);
-        generate_array(new_size);
     };
 
-    let stop_sorting = move |_| {
-        set_sorting.set(false);
-    };
+    share_sort(link, playback, array_size, seed, generate_array, move |values| run.show(values), move || run.start());
 
-    create_effect(move |_| {
-        generate_array(array_size());
+    use_shortcuts(move |shortcut| {
+        if shortcut == Shortcut::Generate && !playback.playing.get_untracked() {
+            set_seed(random_seed());
+        } else {
+            run.handle(shortcut);
+        }
     });
 
     view! {
         <NavBar/>
         <SortingControls
             array_size=array_size
-            is_sorting=sorting
-            on_generate=move |_| generate_array(array_size())
+            is_sorting=playback.playing
+            on_generate=move |_| set_seed(random_seed())
+            seed=seed
+            on_seed_change=move |value| set_seed(value)
             on_sort=bubble_sort
             on_size_change=on_size_change
-        >
-            <button
-                class="px-4 py-2 bg-red-500 text-white rounded hover:bg-red-600 transition-colors"
-                on:click=stop_sorting
-                disabled=move || !sorting.get()
-            >
-                "Stop Sorting"
-            </button>
-        </SortingControls>
+        />
+        <Player playback=playback/>
+        <DownloadAnimation run=run/>
         
         <div class="h-96 flex items-end gap-1">
             {move || {
-                array.get().into_iter().enumerate().map(|(idx, value)| {
+                let frame = frame.get();
+                frame.values.iter().enumerate().map(|(idx, value)| {
                     let height = format!("{}%", value);
-                    let is_comparing = comparison_idx.get()
-                        .map(|(i, j)| idx == i || idx == j)
-                        .unwrap_or(false);
-                    let is_sorted = sorted_indices.get().contains(&idx);
+                    let is_comparing = frame.is_comparing(idx);
+                    let is_sorted = frame.is_sorted(idx);
                     
                     let color = if is_comparing {
                         "#22c55e"
@@ -145,16 +91,7 @@ pub fn BubbleSortVisualizer() -> impl IntoView {
                 }).collect_view()
             }}
         </div>
-        <AlgorithmInfoPanel algorithm_info=BUBBLE_SORT_INFO/>
+        <Narration history=narration/>
+        <AlgorithmInfoPanel algorithm_info=AlgorithmType::Bubble.info() active_line=line/>
     }
 }
-
-fn delay(ms: u64) -> impl std::future::Future<Output = ()> {
-    async move {
-        let (tx, rx) = futures::channel::oneshot::channel::<()>();
-        set_timeout(move || {
-            let _ = tx.send(());
-        }, Duration::from_millis(ms));
-        let _ = rx.await;
-    }
-}
\ No newline at end of file
@@ -0,0 +1,67 @@
+use algoviz_core::animation::{self, AnimationFormat, AnimationOptions};
+use leptos::wasm_bindgen::JsCast;
+use leptos::*;
+use crate::replay::SortRun;
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Saves `bytes` under `file_name` by clicking a temporary link to them.
+pub fn download(file_name: &str, mime_type: &str, bytes: &[u8]) {
+    let Ok(link) = document().create_element("a") else {
+        return;
+    };
+    let _ = link.set_attribute("href", &format!("data:{mime_type};base64,{}", base64(bytes)));
+    let _ = link.set_attribute("download", file_name);
+    link.unchecked_into::<web_sys::HtmlElement>().click();
+}
+
+fn base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (index, &byte)| group | u32::from(byte) << (16 - 8 * index));
+        for index in 0..4 {
+            if index <= chunk.len() {
+                encoded.push(BASE64[(group >> (18 - 6 * index) & 63) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// Saves the run on a sorting page as an animation, a frame per step at the
+/// speed the player is set to.
+#[component]
+pub fn DownloadAnimation(run: SortRun) -> impl IntoView {
+    let button = "px-3 py-1 bg-gray-700 text-white rounded hover:bg-gray-800 transition-colors disabled:opacity-50";
+    let format = create_rw_signal(AnimationFormat::Gif);
+    // only a recorded run has steps to animate
+    let unavailable = move || run.playback.last.get() == 0 || run.playback.playing.get();
+    let save = move |_| {
+        let format = format.get_untracked();
+        let frame_ms = (1000.0 / run.playback.speed.get_untracked()).round() as u32;
+        let options = AnimationOptions { frame_ms, ..AnimationOptions::default() };
+        let file_name = format!("{}-sort.{}", run.algorithm.slug(), format.extension());
+        download(&file_name, format.mime_type(), &animation::render(format, &run.steps(), options));
+    };
+
+    view! {
+        <div class="w-full max-w-5xl mx-auto px-4 my-2 flex items-center justify-center gap-2">
+            <select
+                class="px-2 py-1 border border-gray-300 rounded"
+                on:change=move |ev| format.set(event_target_value(&ev).parse().unwrap_or(AnimationFormat::Gif))
+            >
+                <option value="gif" selected>"GIF"</option>
+                <option value="svg">"SVG"</option>
+            </select>
+            <button
+                class=button
+                on:click=save
+                disabled=unavailable
+            >
+                "Download animation"
+            </button>
+        </div>
+    }
+}
@@ -1,7 +1,4 @@
 mod navbar;
-mod sorting;
-mod sorting_chart;
-mod sorting_menu;
 mod path_finding_menu;
 mod path_finding;
 mod app;
@@ -14,6 +11,13 @@ mod bogosortviz;
 mod sorting_controls;
 mod sorting_info;
 mod landing_page;
+mod seed;
+mod replay;
+mod download;
+mod narration;
+mod player;
+mod share;
+mod shortcuts;
 
 use leptos::*;
 use app::App;
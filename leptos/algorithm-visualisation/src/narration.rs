@@ -0,0 +1,30 @@
+use leptos::*;
+
+/// The explanation of the current step, with the ones before it in a log
+/// that can be opened underneath. `history` ends with the current step.
+#[component]
+pub fn Narration(#[prop(into)] history: Signal<Vec<String>>) -> impl IntoView {
+    let (show_history, set_show_history) = create_signal(false);
+    let current = move || history.with(|history| history.last().cloned().unwrap_or_default());
+
+    view! {
+        <div class="w-full max-w-3xl mx-auto mt-2">
+            <div class="bg-white border border-gray-300 rounded-lg p-3 min-h-12 text-gray-800">
+                {current}
+            </div>
+            <label class="inline-flex items-center gap-2 mt-1 text-sm text-gray-600">
+                <input
+                    type="checkbox"
+                    prop:checked=show_history
+                    on:change=move |ev| set_show_history(event_target_checked(&ev))
+                />
+                "Show history"
+            </label>
+            <Show when=show_history>
+                <ol class="mt-1 max-h-48 overflow-y-auto list-decimal list-inside text-sm text-gray-700 bg-gray-50 rounded-lg p-2">
+                    {move || history.get().into_iter().map(|note| view! { <li>{note}</li> }).collect_view()}
+                </ol>
+            </Show>
+        </div>
+    }
+}
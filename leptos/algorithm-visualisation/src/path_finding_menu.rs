@@ -1,10 +1,14 @@
 use leptos::*;
 use std::collections::{VecDeque, BinaryHeap, HashSet};
 use std::cmp::Ordering;
-use gloo_timers::future::TimeoutFuture;
 use rand::prelude::SliceRandom;
 use rand::Rng;
+use crate::narration::Narration;
 use crate::navbar::NavBar;
+use crate::player::{Playback, Player};
+use crate::seed::{random_seed, seeded_rng, SeedInput};
+use crate::share::{format_speed, opening_query, param, sync_query};
+use crate::shortcuts::{use_shortcuts, Shortcut};
 
 // Constants
 const ROWS: usize = 20;
@@ -46,11 +50,41 @@ enum Algorithm {
     Swarm,
 }
 
-#[derive(Clone, PartialEq)]
-enum AnimationSpeed {
-    Slow,
-    Medium,
-    Fast,
+impl Algorithm {
+    /// Name used by the algorithm select and in shared links.
+    fn slug(&self) -> &'static str {
+        match self {
+            Algorithm::Dijkstra => "dijkstra",
+            Algorithm::AStar => "astar",
+            Algorithm::BFS => "bfs",
+            Algorithm::DFS => "dfs",
+            Algorithm::Swarm => "swarm",
+        }
+    }
+
+    fn from_slug(slug: &str) -> Option<Self> {
+        [Algorithm::Dijkstra, Algorithm::AStar, Algorithm::BFS, Algorithm::DFS, Algorithm::Swarm]
+            .into_iter()
+            .find(|algorithm| algorithm.slug() == slug)
+    }
+
+    /// Why the search expands `cell` next.
+    fn explain_expansion(&self, cell: (usize, usize), end: (usize, usize)) -> String {
+        let (row, col) = cell;
+        match self {
+            Algorithm::Dijkstra => format!("Dijkstra expands ({row}, {col}), the unexpanded cell closest to the start."),
+            Algorithm::AStar => format!(
+                "A* expands ({row}, {col}): its distance from the start plus its estimate of {} more to the end is the lowest.",
+                heuristic(cell, end),
+            ),
+            Algorithm::BFS => format!("BFS takes ({row}, {col}) from the front of the queue; its unseen neighbours join the back."),
+            Algorithm::DFS => format!("DFS takes ({row}, {col}) from the top of the stack and carries on from there."),
+            Algorithm::Swarm => format!(
+                "The swarm expands ({row}, {col}), pulled towards the end {} cells away.",
+                manhattan_distance(cell, end),
+            ),
+        }
+    }
 }
 
 #[derive(Copy, Clone, Eq, PartialEq)]
@@ -74,30 +108,29 @@ impl PartialOrd for State {
 // Main component
 #[component]
 pub fn PathfindingVisualizer() -> impl IntoView {
-    let (grid, set_grid) = create_signal(vec![vec![Node { row: 0, col: 0, node_type: NodeType::Empty }; COLS]; ROWS]);
-    let (start_node, set_start_node) = create_signal(None::<(usize, usize)>);
-    let (end_node, set_end_node) = create_signal(None::<(usize, usize)>);
+    // a shared link brings its grid, algorithm and progress along
+    let link = opening_query();
+    let linked_start = link.get("start").and_then(|cell| parse_cell(cell));
+    let linked_end = link.get("end").and_then(|cell| parse_cell(cell)).filter(|&end| Some(end) != linked_start);
+    let linked_walls = link.get("walls").map(|walls| decode_walls(walls)).unwrap_or_default();
+    let (grid, set_grid) = create_signal(linked_grid(&linked_walls, linked_start, linked_end));
+    let (start_node, set_start_node) = create_signal(linked_start);
+    let (end_node, set_end_node) = create_signal(linked_end);
     let (is_mouse_pressed, set_is_mouse_pressed) = create_signal(false);
-    let (selected_algorithm, set_selected_algorithm) = create_signal(Algorithm::Dijkstra);
+    let (selected_algorithm, set_selected_algorithm) =
+        create_signal(link.get("algorithm").and_then(|slug| Algorithm::from_slug(slug)).unwrap_or(Algorithm::Dijkstra));
     let (current_mode, set_current_mode) = create_signal(NodeType::Empty);
-    let (is_animating, set_is_animating) = create_signal(false);
     let (visited_nodes, set_visited_nodes) = create_signal(Vec::new());
     let (path, set_path) = create_signal(Vec::new());
-    let (animation_speed, set_animation_speed) = create_signal(AnimationSpeed::Medium);
+    let playback = Playback::new(param(&link, "speed").unwrap_or(150.0));
     let (wall_drawing_mode, set_wall_drawing_mode) = create_signal(DrawingMode::None);
-
-    create_effect(move |_| {
-        let mut new_grid = vec![vec![Node { row: 0, col: 0, node_type: NodeType::Empty }; COLS]; ROWS];
-        for i in 0..ROWS {
-            for j in 0..COLS {
-                new_grid[i][j] = Node {
-                    row: i,
-                    col: j,
-                    node_type: NodeType::Empty,
-                };
-            }
-        }
-        set_grid(new_grid);
+    let (maze_seed, set_maze_seed) = create_signal(random_seed());
+    // one explanation per step of the search, then how it ended
+    let (notes, set_notes) = create_signal(Vec::<String>::new());
+    let narration = Signal::derive(move || {
+        let step = playback.step.get();
+        let finished = step > 0 && step >= playback.last.get();
+        notes.with(|notes| notes[..if finished { notes.len() } else { step.min(notes.len()) }].to_vec())
     });
 
     let handle_mouse_down = move |row: usize, col: usize, e: web_sys::MouseEvent| {
@@ -171,38 +204,74 @@ pub fn PathfindingVisualizer() -> impl IntoView {
         }).collect::<Vec<_>>()
     };
 
-    let visualize_pathfinding = move |_| {
-        if !is_animating.get() {
-            set_is_animating(true);
-            if let (Some(start), Some(end)) = (start_node.get(), end_node.get()) {
-                let algorithm = selected_algorithm.get();
-                
-                set_grid.update(|g| clear_path_and_visited(g));
-                
-                let current_grid = grid.get();
-                
-                let (visited, path_result) = match algorithm {
-                    Algorithm::Dijkstra => dijkstra(&current_grid, start, end),
-                    Algorithm::AStar => astar(&current_grid, start, end),
-                    Algorithm::BFS => bfs(&current_grid, start, end),
-                    Algorithm::DFS => dfs(&current_grid, start, end),
-                    Algorithm::Swarm => swarm(&current_grid, start, end),
-                };
-    
-                set_visited_nodes(visited);
-                set_path(path_result);
-    
-                spawn_local(async move {
-                    animate_path_finding(set_grid, visited_nodes, path, set_is_animating, animation_speed).await;
-                });
-            } else {
-                set_is_animating(false);
-            }
+    // draws the loaded search as far as the player has got
+    create_effect(move |_| {
+        let step = playback.step.get();
+        let finished = step >= playback.last.get_untracked();
+        visited_nodes.with_untracked(|visited| {
+            path.with_untracked(|path| set_grid.update(|g| show_search(g, visited, path, step, finished)))
+        });
+    });
+
+    let forget_search = move || {
+        set_visited_nodes(Vec::new());
+        set_path(Vec::new());
+        set_notes(Vec::new());
+        playback.load(0);
+    };
+
+    let visualize_pathfinding = move || {
+        if let (Some(start), Some(end)) = (start_node.get(), end_node.get()) {
+            let algorithm = selected_algorithm.get();
+
+            set_grid.update(|g| clear_path_and_visited(g));
+
+            let current_grid = grid.get();
+
+            let (visited, path_result) = match algorithm {
+                Algorithm::Dijkstra => dijkstra(&current_grid, start, end),
+                Algorithm::AStar => astar(&current_grid, start, end),
+                Algorithm::BFS => bfs(&current_grid, start, end),
+                Algorithm::DFS => dfs(&current_grid, start, end),
+                Algorithm::Swarm => swarm(&current_grid, start, end),
+            };
+
+            let steps = search_steps(&visited, &path_result);
+            set_notes(narrate_search(&algorithm, &visited, &path_result, end));
+            set_visited_nodes(visited);
+            set_path(path_result);
+            playback.load(steps);
+            playback.play();
         }
     };
 
-    let generate_labyrinth = move |_| {
+    if let Some(step) = param::<usize>(&link, "step").filter(|&step| step > 0) {
+        visualize_pathfinding();
+        playback.pause();
+        playback.seek(step);
+    }
+
+    sync_query(move || {
+        if playback.playing.get() {
+            return None;
+        }
+        let mut query = format!("algorithm={}", selected_algorithm.get().slug());
+        let walls = grid.with(|grid| encode_walls(grid));
+        if !walls.is_empty() {
+            query.push_str(&format!("&walls={walls}"));
+        }
+        for (key, cell) in [("start", start_node.get()), ("end", end_node.get())] {
+            if let Some((row, col)) = cell {
+                query.push_str(&format!("&{key}={row},{col}"));
+            }
+        }
+        query.push_str(&format!("&speed={}&step={}", format_speed(playback.speed.get()), playback.step.get()));
+        Some(query)
+    });
+
+    let build_labyrinth = move |seed: u64| {
         if let (Some(start), Some(end)) = (start_node.get(), end_node.get()) {
+            forget_search();
             set_grid.update(|g| {
                 for row in g.iter_mut() {
                     for node in row.iter_mut() {
@@ -210,7 +279,7 @@ pub fn PathfindingVisualizer() -> impl IntoView {
                     }
                 }
 
-                generate_maze(g, start, end);
+                generate_maze(g, start, end, seed);
 
                 g[start.0][start.1].node_type = NodeType::Start;
                 g[end.0][end.1].node_type = NodeType::End;
@@ -218,28 +287,52 @@ pub fn PathfindingVisualizer() -> impl IntoView {
         }
     };
 
-    let clear_grid = move |_| {
+    let generate_labyrinth = move || {
+        let seed = random_seed();
+        set_maze_seed(seed);
+        build_labyrinth(seed);
+    };
+
+    let on_maze_seed_change = move |seed: u64| {
+        set_maze_seed(seed);
+        build_labyrinth(seed);
+    };
+
+    let clear_grid = move || {
         initialize_grid(&set_grid);
         set_start_node(None);
         set_end_node(None);
-        set_is_animating(false);
+        forget_search();
     };
 
+    use_shortcuts(move |shortcut| match shortcut {
+        // once a search has finished, space searches the grid as it is now
+        Shortcut::TogglePlay if !playback.playing.get_untracked() && playback.step.get_untracked() >= playback.last.get_untracked() => {
+            visualize_pathfinding()
+        }
+        Shortcut::Generate if !playback.playing.get_untracked() && start_node.get_untracked().is_some() && end_node.get_untracked().is_some() => {
+            generate_labyrinth()
+        }
+        Shortcut::Reset => clear_grid(),
+        Shortcut::WallMode => set_current_mode(NodeType::Wall),
+        Shortcut::StartMode => set_current_mode(NodeType::Start),
+        Shortcut::EndMode => set_current_mode(NodeType::End),
+        _ => {
+            playback.handle(shortcut);
+        }
+    });
+
     view! {
         <NavBar/>
         <div class="pathfinding-visualizer mt-2" style="display: flex; flex-direction: column; align-items: center;">
             //<h1>"Pathfinding Visualizer"</h1>
             <div class="controls" style="margin-bottom: 20px;">
-                <select on:change=move |ev| {
-                    set_selected_algorithm(match event_target_value(&ev).as_str() {
-                        "dijkstra" => Algorithm::Dijkstra,
-                        "astar" => Algorithm::AStar,
-                        "bfs" => Algorithm::BFS,
-                        "dfs" => Algorithm::DFS,
-                        "swarm" => Algorithm::Swarm,
-                        _ => Algorithm::Dijkstra,
-                    });
-                }>
+                <select
+                    on:change=move |ev| {
+                        set_selected_algorithm(Algorithm::from_slug(&event_target_value(&ev)).unwrap_or(Algorithm::Dijkstra));
+                    }
+                    prop:value=move || selected_algorithm.get().slug()
+                >
                     <option value="dijkstra">"Dijkstra's Algorithm"</option>
                     <option value="astar">"A* Search"</option>
                     <option value="bfs">"Breadth-First Search"</option>
@@ -248,35 +341,48 @@ pub fn PathfindingVisualizer() -> impl IntoView {
                 </select>
                 <button
                 class="ml-1 px-4 py-2 bg-blue-500 text-white rounded"
-                on:click=visualize_pathfinding disabled=is_animating>"Visualize Pathfinding"</button>
+                on:click=move |_| visualize_pathfinding() disabled=playback.playing
+                title="Visualize Pathfinding (space)">"Visualize Pathfinding"</button>
             </div>
 
             <div class="grid" style="display: inline-block; border: 1px solid #000;">
                 {render_grid}
             </div>
+            <Player playback=playback/>
+            <Narration history=narration/>
             <div class="node-selection" style="margin-bottom: 10px;">
                 <button
                 class="m-1 px-4 py-2 bg-blue-500 text-white rounded"
-                on:click=move |_| set_current_mode(NodeType::Start)>"Select Start"</button>
+                on:click=move |_| set_current_mode(NodeType::Start)
+                title="Select Start (S)">"Select Start"</button>
                 <button
                 class="m-1 px-4 py-2 bg-blue-500 text-white rounded"
-                on:click=move |_| set_current_mode(NodeType::End)>"Select End"</button>
+                on:click=move |_| set_current_mode(NodeType::End)
+                title="Select End (E)">"Select End"</button>
                 <button
                 class="m-1 px-4 py-2 bg-blue-500 text-white rounded"
-                on:click=move |_| set_current_mode(NodeType::Wall)>"Draw Walls"</button>
+                on:click=move |_| set_current_mode(NodeType::Wall)
+                title="Draw Walls (W)">"Draw Walls"</button>
                 <button
                     class="m-1 px-4 py-2 bg-blue-500 text-white rounded"
-                    on:click=generate_labyrinth
+                    on:click=move |_| generate_labyrinth()
+                    title="Generate Labyrinth (G)"
                     disabled={move || start_node.get().is_none() || end_node.get().is_none()}
                 >
                     "Generate Labyrinth"
                 </button>
+                <SeedInput
+                    seed=maze_seed
+                    on_seed_change=on_maze_seed_change
+                    disabled=Signal::derive(move || playback.playing.get() || start_node.get().is_none() || end_node.get().is_none())
+                />
                 <button
                 class="m-1 px-4 py-2 bg-blue-500 text-white rounded"
-                on:click=clear_grid>"Clear Grid"</button>
+                on:click=move |_| clear_grid()
+                title="Clear Grid (R)">"Clear Grid"</button>
                 // <button
                 // class="px-4 py-2 bg-blue-500 text-white rounded"
-                // on:click=visualize_pathfinding disabled=is_animating>"Visualize Pathfinding"</button>
+                // on:click=visualize_pathfinding disabled=playback.playing>"Visualize Pathfinding"</button>
             </div>
             <p>
                 "Start: " {move || start_node.get().map(|(r, c)| format!("({}, {})", r, c)).unwrap_or_else(|| "Not set".to_string())}
@@ -284,24 +390,64 @@ pub fn PathfindingVisualizer() -> impl IntoView {
             <p>
                 "End: " {move || end_node.get().map(|(r, c)| format!("({}, {})", r, c)).unwrap_or_else(|| "Not set".to_string())}
             </p>
-                <div class="speed-control" style="margin-top: 10px;">
-                    <label>"Animation Speed: "</label>
-                    <select on:change=move |ev| {
-                        set_animation_speed(match event_target_value(&ev).as_str() {
-                            "slow" => AnimationSpeed::Slow,
-                            "fast" => AnimationSpeed::Fast,
-                            _ => AnimationSpeed::Medium,
-                        });
-                    }>
-                        <option value="slow">"Slow"</option>
-                        <option value="medium" selected="true">"Medium"</option>
-                        <option value="fast">"Fast"</option>
-                    </select>
-                </div>
         </div>
     }
 }
 
+/// Characters of a shared grid's walls, six cells to a character.
+const WALL_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Packs the walls into a link, six cells to a character row by row, and
+/// leaves off the empty cells at the end.
+fn encode_walls(grid: &[Vec<Node>]) -> String {
+    let walls: Vec<bool> = grid.iter().flatten().map(|node| node.node_type == NodeType::Wall).collect();
+    let encoded: String = walls
+        .chunks(6)
+        .map(|chunk| {
+            let bits = chunk.iter().enumerate().fold(0, |bits, (bit, &wall)| bits | (usize::from(wall) << bit));
+            char::from(WALL_ALPHABET[bits])
+        })
+        .collect();
+    encoded.trim_end_matches('A').to_string()
+}
+
+fn decode_walls(encoded: &str) -> Vec<(usize, usize)> {
+    encoded
+        .bytes()
+        .enumerate()
+        .flat_map(|(position, byte)| {
+            let bits = WALL_ALPHABET.iter().position(|&character| character == byte).unwrap_or(0);
+            (0..6).filter(move |bit| (bits >> bit) & 1 == 1).map(move |bit| position * 6 + bit)
+        })
+        .filter(|&cell| cell < ROWS * COLS)
+        .map(|cell| (cell / COLS, cell % COLS))
+        .collect()
+}
+
+/// Reads a `row,col` cell from a link.
+fn parse_cell(cell: &str) -> Option<(usize, usize)> {
+    let (row, col) = cell.split_once(',')?;
+    let cell = (row.trim().parse().ok()?, col.trim().parse().ok()?);
+    (cell.0 < ROWS && cell.1 < COLS).then_some(cell)
+}
+
+/// The grid a link describes; an empty one without a link.
+fn linked_grid(walls: &[(usize, usize)], start: Option<(usize, usize)>, end: Option<(usize, usize)>) -> Vec<Vec<Node>> {
+    let mut grid: Vec<Vec<Node>> = (0..ROWS)
+        .map(|row| (0..COLS).map(|col| Node { row, col, node_type: NodeType::Empty }).collect())
+        .collect();
+    for &(row, col) in walls {
+        grid[row][col].node_type = NodeType::Wall;
+    }
+    if let Some((row, col)) = start {
+        grid[row][col].node_type = NodeType::Start;
+    }
+    if let Some((row, col)) = end {
+        grid[row][col].node_type = NodeType::End;
+    }
+    grid
+}
+
 fn clear_path_and_visited(grid: &mut Vec<Vec<Node>>) {
     for row in grid.iter_mut() {
         for node in row.iter_mut() {
@@ -312,63 +458,50 @@ fn clear_path_and_visited(grid: &mut Vec<Vec<Node>>) {
     }
 }
 
-async fn animate_path_finding(
-    set_grid: WriteSignal<Vec<Vec<Node>>>,
-    visited_nodes: ReadSignal<Vec<(usize, usize)>>,
-    path: ReadSignal<Vec<(usize, usize)>>,
-    set_is_animating: WriteSignal<bool>,
-    animation_speed: ReadSignal<AnimationSpeed>,
-) {
-    let speed = match animation_speed.get() {
-        AnimationSpeed::Slow => 50,
-        AnimationSpeed::Medium => 20,
-        AnimationSpeed::Fast => 5,
-    };
-
-    let batch_size = match animation_speed.get() {
-        AnimationSpeed::Slow => 1,
-        AnimationSpeed::Medium => 3,
-        AnimationSpeed::Fast => 5,
-    };
-
-    let start = visited_nodes.get().first().cloned();
-    let end = path.get().last().cloned();
-    let visited = visited_nodes.get();
+/// Number of steps in a search: one per expanded cell, then one per cell of
+/// the path if it reached the end. Without a way through, the path is just
+/// the end on its own.
+fn search_steps(visited: &[(usize, usize)], path: &[(usize, usize)]) -> usize {
+    visited.len() + if path.len() > 1 { path.len() } else { 0 }
+}
 
-    for chunk in visited.chunks(batch_size) {
-        set_grid.update(|g| {
-            for &(row, col) in chunk {
-                if Some((row, col)) != start && Some((row, col)) != end {
-                    g[row][col].node_type = NodeType::Visited;
-                }
+/// Paints the first `step` steps of a search over the grid. A finished
+/// search leaves only its path.
+fn show_search(grid: &mut Vec<Vec<Node>>, visited: &[(usize, usize)], path: &[(usize, usize)], step: usize, finished: bool) {
+    clear_path_and_visited(grid);
+    let expanded = step.min(visited.len());
+    if !finished {
+        for &(row, col) in &visited[..expanded] {
+            if grid[row][col].node_type == NodeType::Empty {
+                grid[row][col].node_type = NodeType::Visited;
             }
-        });
-        TimeoutFuture::new(speed).await;
+        }
     }
-
-    for chunk in path.get().chunks(batch_size) {
-        set_grid.update(|g| {
-            for &(row, col) in chunk {
-                if Some((row, col)) != start && Some((row, col)) != end {
-                    g[row][col].node_type = NodeType::Path;
-                }
-            }
-        });
-        TimeoutFuture::new(speed).await;
+    let traced = (step - expanded).min(path.len());
+    for &(row, col) in &path[..traced] {
+        if matches!(grid[row][col].node_type, NodeType::Empty | NodeType::Visited) {
+            grid[row][col].node_type = NodeType::Path;
+        }
     }
+}
 
-    set_grid.update(|g| {
-        for &(row, col) in &visited_nodes.get() {
-            if Some((row, col)) != start && Some((row, col)) != end && !path.get().contains(&(row, col)) {
-                g[row][col].node_type = NodeType::Empty;
-            }
-        }
-    });
-    set_is_animating(false);
+/// What each step of a search does, then how the search ended.
+fn narrate_search(algorithm: &Algorithm, visited: &[(usize, usize)], path: &[(usize, usize)], end: (usize, usize)) -> Vec<String> {
+    let reached = path.len() > 1;
+    let mut notes: Vec<String> = visited.iter().map(|&cell| algorithm.explain_expansion(cell, end)).collect();
+    if reached {
+        notes.extend(path.iter().enumerate().map(|(index, &(row, col))| {
+            format!("Tracing the path from the start: ({row}, {col}), cell {} of {}.", index + 1, path.len())
+        }));
+        notes.push(format!("Reached the end after expanding {} cells; the path is {} steps long.", visited.len(), path.len() - 1));
+    } else {
+        notes.push(format!("Expanded all {} reachable cells without finding the end; it is walled off.", visited.len()));
+    }
+    notes
 }
 
-fn generate_maze(grid: &mut Vec<Vec<Node>>, start: (usize, usize), end: (usize, usize)) {
-    let mut rng = rand::thread_rng();
+fn generate_maze(grid: &mut Vec<Vec<Node>>, start: (usize, usize), end: (usize, usize), seed: u64) {
+    let mut rng = seeded_rng(seed);
     let mut stack = vec![start];
     let mut visited = HashSet::new();
     visited.insert(start);
@@ -0,0 +1,173 @@
+use leptos::*;
+use std::time::Duration;
+
+/// Slowest and fastest playback, in steps per second.
+const MIN_SPEED: f64 = 1.0;
+const MAX_SPEED: f64 = 1000.0;
+/// How much one press of `+` or `-` changes the speed.
+const SPEED_FACTOR: f64 = 1.5;
+/// Shortest wait between two ticks; faster speeds move several steps per tick.
+const TICK_MS: f64 = 16.0;
+
+/// Where a player stands in a precomputed run. Pages draw whatever `step`
+/// says and call `load` whenever they record a new run.
+#[derive(Clone, Copy)]
+pub struct Playback {
+    pub step: RwSignal<usize>,
+    /// The last step; step 0 shows the run before it started.
+    pub last: RwSignal<usize>,
+    pub playing: RwSignal<bool>,
+    /// Steps per second.
+    pub speed: RwSignal<f64>,
+}
+
+impl Playback {
+    /// Starts out empty at `speed` steps per second, with the timer that
+    /// moves `step` on while playing.
+    pub fn new(speed: f64) -> Self {
+        let playback = Playback {
+            step: create_rw_signal(0),
+            last: create_rw_signal(0),
+            playing: create_rw_signal(false),
+            speed: create_rw_signal(speed.clamp(MIN_SPEED, MAX_SPEED)),
+        };
+        // re-run on every step, so pausing or seeking drops the pending tick
+        create_effect(move |_| {
+            if !playback.playing.get() || playback.step.get() >= playback.last.get() {
+                return;
+            }
+            let interval = 1000.0 / playback.speed.get();
+            let wait = interval.max(TICK_MS);
+            let count = (wait / interval).round().max(1.0) as usize;
+            if let Ok(handle) = set_timeout_with_handle(move || playback.forward(count), Duration::from_secs_f64(wait / 1000.0)) {
+                on_cleanup(move || handle.clear());
+            }
+        });
+        playback
+    }
+
+    /// Rewinds to the start of a run with `last` steps, paused.
+    pub fn load(&self, last: usize) {
+        self.playing.set(false);
+        self.last.set(last);
+        self.step.set(0);
+    }
+
+    /// Starts playing; a finished run starts over.
+    pub fn play(&self) {
+        if self.step.get_untracked() >= self.last.get_untracked() {
+            self.step.set(0);
+        }
+        self.playing.set(self.last.get_untracked() > 0);
+    }
+
+    pub fn pause(&self) {
+        self.playing.set(false);
+    }
+
+    pub fn toggle(&self) {
+        if self.playing.get_untracked() {
+            self.pause();
+        } else {
+            self.play();
+        }
+    }
+
+    /// Moves up to `count` steps on and stops playing at the end.
+    pub fn forward(&self, count: usize) {
+        let last = self.last.get_untracked();
+        let step = (self.step.get_untracked() + count).min(last);
+        self.step.set(step);
+        if step == last {
+            self.playing.set(false);
+        }
+    }
+
+    pub fn back(&self) {
+        self.pause();
+        self.step.update(|step| *step = step.saturating_sub(1));
+    }
+
+    pub fn seek(&self, step: usize) {
+        self.step.set(step.min(self.last.get_untracked()));
+    }
+
+    pub fn faster(&self) {
+        self.speed.update(|speed| *speed = (*speed * SPEED_FACTOR).min(MAX_SPEED));
+    }
+
+    pub fn slower(&self) {
+        self.speed.update(|speed| *speed = (*speed / SPEED_FACTOR).max(MIN_SPEED));
+    }
+}
+
+/// The speed slider moves along a logarithmic scale from 0 to 1, so slow
+/// speeds get as much room as fast ones.
+fn speed_at(position: f64) -> f64 {
+    MIN_SPEED * (MAX_SPEED / MIN_SPEED).powf(position)
+}
+
+fn position_of(speed: f64) -> f64 {
+    (speed / MIN_SPEED).ln() / (MAX_SPEED / MIN_SPEED).ln()
+}
+
+#[component]
+pub fn Player(playback: Playback) -> impl IntoView {
+    let button = "px-3 py-1 bg-gray-700 text-white rounded hover:bg-gray-800 transition-colors disabled:opacity-50";
+    let at_start = move || playback.step.get() == 0;
+    let at_end = move || playback.step.get() >= playback.last.get();
+
+    view! {
+        <div class="w-full max-w-5xl mx-auto px-4 my-2 flex items-center justify-center gap-2 flex-wrap">
+            <button class=button on:click=move |_| playback.seek(0) disabled=at_start title="Back to the start">
+                "⏮"
+            </button>
+            <button class=button on:click=move |_| playback.back() disabled=at_start title="Step back (←)">
+                "◀"
+            </button>
+            <button
+                class=button
+                on:click=move |_| playback.toggle()
+                disabled=move || playback.last.get() == 0
+                title="Play or pause (space)"
+            >
+                {move || if playback.playing.get() { "Pause" } else { "Play" }}
+            </button>
+            <button class=button on:click=move |_| playback.forward(1) disabled=at_end title="Step forward (→)">
+                "▶"
+            </button>
+            <input
+                type="range"
+                min="0"
+                max=move || playback.last.get().to_string()
+                prop:value=move || playback.step.get().to_string()
+                on:input=move |ev| {
+                    if let Ok(step) = event_target_value(&ev).parse() {
+                        playback.seek(step);
+                    }
+                }
+                class="w-64"
+            />
+            <span class="tabular-nums whitespace-nowrap">
+                "Step " {move || playback.step.get()} " / " {move || playback.last.get()}
+            </span>
+            <label class="ml-4 whitespace-nowrap" title="Faster (+) or slower (-)">"Speed"</label>
+            <input
+                type="range"
+                min="0"
+                max="1"
+                step="0.01"
+                prop:value=move || position_of(playback.speed.get()).to_string()
+                on:input=move |ev| {
+                    if let Ok(position) = event_target_value(&ev).parse() {
+                        playback.speed.set(speed_at(position));
+                    }
+                }
+                class="w-32"
+            />
+            <span class="tabular-nums whitespace-nowrap">
+                {move || format!("{:.0} steps/s", playback.speed.get())}
+            </span>
+        </div>
+    }
+}
@@ -0,0 +1,102 @@
+pub use algoviz_core::replay::{Frame, Replay};
+use algoviz_core::{narration, pseudocode};
+use algoviz_core::sorting::{self, AlgorithmType};
+use algoviz_core::steps::Steps;
+use algoviz_core::trace::Operation;
+use leptos::*;
+use crate::player::Playback;
+
+/// Sorts `values` with the shared core and returns every operation it performed.
+pub fn record(algorithm: AlgorithmType, values: Vec<i64>) -> Vec<Operation> {
+    sorting::sort(algorithm, values, Vec::new()).operations
+}
+
+/// The sort a visualizer page shows, drawn at whatever step its player is on.
+#[derive(Clone, Copy)]
+pub struct SortRun {
+    pub algorithm: AlgorithmType,
+    pub playback: Playback,
+    /// The bars at the current step.
+    pub frame: RwSignal<Frame>,
+    /// The pseudocode line the current step carries out.
+    pub line: RwSignal<Option<usize>>,
+    /// What the steps so far did, in words, ending with the current one.
+    pub narration: Signal<Vec<String>>,
+    replay: StoredValue<Replay>,
+    lines: StoredValue<Vec<Option<usize>>>,
+    notes: StoredValue<Vec<String>>,
+}
+
+impl SortRun {
+    /// An empty run played at `speed` steps per second to begin with.
+    pub fn new(algorithm: AlgorithmType, speed: f64) -> Self {
+        let playback = Playback::new(speed);
+        let notes = store_value(Vec::<String>::new());
+        let narration = Signal::derive(move || {
+            let (step, last) = (playback.step.get(), playback.last.get());
+            // a finished run also tells how it ended
+            notes.with_value(|notes| notes[..if step >= last { notes.len() } else { (step + 1).min(notes.len()) }].to_vec())
+        });
+        let run = SortRun {
+            algorithm,
+            playback,
+            frame: create_rw_signal(Frame::default()),
+            line: create_rw_signal(None),
+            narration,
+            replay: store_value(Replay::default()),
+            lines: store_value(Vec::new()),
+            notes,
+        };
+        create_effect(move |_| {
+            let step = run.playback.step.get();
+            run.replay.update_value(|replay| replay.seek(step));
+            let (frame, line) = run.replay.with_value(|replay| {
+                // the first and last steps show the array rather than any line
+                let line = if step == 0 || step >= replay.steps() {
+                    None
+                } else {
+                    run.lines.with_value(|lines| lines.get(replay.applied() - 1).copied().flatten())
+                };
+                (replay.frame().clone(), line)
+            });
+            run.frame.set(frame);
+            run.line.set(line);
+        });
+        run
+    }
+
+    /// Shows `values` unsorted, dropping whatever run was loaded.
+    pub fn show(&self, values: Vec<i64>) {
+        self.replay.set_value(Replay::new(values, Vec::new()));
+        self.lines.set_value(Vec::new());
+        self.notes.set_value(Vec::new());
+        self.playback.load(0);
+        self.frame.set(self.replay.with_value(|replay| replay.frame().clone()));
+        self.line.set(None);
+    }
+
+    /// Records the sort of the values on screen and starts playing it.
+    pub fn start(&self) {
+        let values = self.replay.with_value(|replay| replay.input().to_vec());
+        let operations = record(self.algorithm, values.clone());
+        self.lines.set_value(pseudocode::lines(self.algorithm, &operations));
+        self.notes.set_value(narration::narrate_steps(self.algorithm, values.clone(), &operations));
+        self.replay.set_value(Replay::new(values, operations));
+        self.playback.load(self.replay.with_value(Replay::steps));
+        self.playback.play();
+    }
+
+    /// Drops the recorded run and shows its input unsorted again.
+    pub fn reset(&self) {
+        self.show(self.replay.with_value(|replay| replay.input().to_vec()));
+    }
+
+    /// Every step of the recorded run, as the animations draw them.
+    pub fn steps(&self) -> Steps {
+        self.replay.with_value(|replay| Steps::from_trace(self.algorithm, replay.input().to_vec(), replay.operations()))
+    }
+
+    pub fn with_replay<T>(&self, f: impl FnOnce(&Replay) -> T) -> T {
+        self.replay.with_value(f)
+    }
+}
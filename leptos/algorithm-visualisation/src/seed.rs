@@ -0,0 +1,38 @@
+use leptos::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Picks a fresh seed for a new array, maze or shuffle sequence.
+pub fn random_seed() -> u64 {
+    rand::thread_rng().gen()
+}
+
+/// Generator for `seed`; the same seed always produces the same run.
+pub fn seeded_rng(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+#[component]
+pub fn SeedInput(
+    #[prop(into)] seed: Signal<u64>,
+    #[prop(into)] on_seed_change: Callback<u64>,
+    #[prop(into)] disabled: Signal<bool>,
+) -> impl IntoView {
+    view! {
+        <div class="inline-flex items-center gap-2">
+            <label class="whitespace-nowrap">"Seed: "</label>
+            <input
+                type="number"
+                min="0"
+                class="w-48 px-2 py-1 border rounded"
+                prop:value=move || seed.get().to_string()
+                on:change=move |ev| {
+                    if let Ok(value) = event_target_value(&ev).trim().parse::<u64>() {
+                        on_seed_change.call(value);
+                    }
+                }
+                disabled=disabled
+            />
+        </div>
+    }
+}
@@ -0,0 +1,116 @@
+use std::str::FromStr;
+use leptos::*;
+use leptos::wasm_bindgen::JsValue;
+use leptos_router::{use_query_map, ParamsMap};
+use crate::player::Playback;
+
+/// Largest array a link may ask for, as far as the size slider goes.
+const MAX_LINKED_SIZE: usize = 150;
+
+/// Reads `key` from a page's query, or `None` when it is missing or malformed.
+pub fn param<T: FromStr>(query: &ParamsMap, key: &str) -> Option<T> {
+    query.get(key).and_then(|value| value.trim().parse().ok())
+}
+
+/// The query the page was opened with.
+pub fn opening_query() -> ParamsMap {
+    use_query_map().get_untracked()
+}
+
+/// Keeps the address bar pointing at the query `query` builds, so copying
+/// the link shares the page as it is. `None` leaves the link alone, which
+/// pages return while playing rather than rewrite it on every step.
+///
+/// The history entry is replaced behind the router's back: pages only read
+/// their query when they open, and rerouting on every edit would be wasted.
+pub fn sync_query(query: impl Fn() -> Option<String> + 'static) {
+    create_effect(move |_| {
+        if let Some(query) = query() {
+            if let Ok(history) = window().history() {
+                let _ = history.replace_state_with_url(&JsValue::NULL, "", Some(&format!("?{query}")));
+            }
+        }
+    });
+}
+
+/// Speeds come off a logarithmic slider; two decimals keep the link short.
+pub fn format_speed(speed: f64) -> String {
+    format!("{}", (speed * 100.0).round() / 100.0)
+}
+
+/// What a sorting page's link records. The array is the one `seed`
+/// generates at `size`, unless the link spells it out in `array`.
+pub struct SortLink {
+    pub array: Option<Vec<i64>>,
+    pub size: Option<usize>,
+    pub seed: Option<u64>,
+    pub speed: Option<f64>,
+    pub step: Option<usize>,
+}
+
+impl SortLink {
+    pub fn read() -> Self {
+        let query = opening_query();
+        let array = query
+            .get("array")
+            .map(|values| values.split(',').filter_map(|value| value.trim().parse().ok()).collect::<Vec<i64>>())
+            .filter(|array| !array.is_empty() && array.len() <= MAX_LINKED_SIZE);
+        SortLink {
+            size: array.as_ref().map(Vec::len).or_else(|| param(&query, "size")).filter(|&size| size <= MAX_LINKED_SIZE),
+            array,
+            seed: param(&query, "seed"),
+            speed: param(&query, "speed"),
+            step: param(&query, "step"),
+        }
+    }
+}
+
+/// Sets a sorting page up from `link` and keeps the link up to date
+/// afterwards. Stands in for the page's own effect regenerating the array
+/// whenever `size` or `seed` change: `generate_array` does that, `show`
+/// puts a linked array on screen and `record` sorts whatever is shown.
+pub fn share_sort(
+    link: SortLink,
+    playback: Playback,
+    size: impl Into<Signal<usize>>,
+    seed: impl Into<Signal<u64>>,
+    generate_array: impl Fn(usize, u64) + 'static,
+    show: impl Fn(Vec<i64>) + 'static,
+    record: impl Fn() + 'static,
+) {
+    let (size, seed) = (size.into(), seed.into());
+    // a linked array stays until the controls ask for another one
+    let linked = create_rw_signal(link.array);
+    create_effect(move |opened: Option<()>| {
+        let (size, seed) = (size.get(), seed.get());
+        if opened.is_some() {
+            linked.set(None);
+        }
+        match linked.get_untracked() {
+            Some(array) => show(array),
+            None => generate_array(size, seed),
+        }
+        if let (None, Some(step)) = (opened, link.step.filter(|&step| step > 0)) {
+            record();
+            playback.pause();
+            playback.seek(step);
+        }
+    });
+
+    sync_query(move || {
+        if playback.playing.get() {
+            return None;
+        }
+        let array = linked.with(|linked| match linked {
+            Some(array) => format!("array={}", array.iter().map(i64::to_string).collect::<Vec<_>>().join(",")),
+            None => format!("size={}", size.get()),
+        });
+        // the seed goes along with an explicit array too, for the shuffles bogo sort draws from it
+        Some(format!(
+            "{array}&seed={}&speed={}&step={}",
+            seed.get(),
+            format_speed(playback.speed.get()),
+            playback.step.get(),
+        ))
+    });
+}
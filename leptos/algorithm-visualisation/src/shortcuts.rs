@@ -0,0 +1,100 @@
+use leptos::wasm_bindgen::JsCast;
+use leptos::*;
+use crate::player::Playback;
+use crate::replay::SortRun;
+
+/// What a key press asks for. Each page handles the ones that make sense
+/// on it and ignores the rest.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Shortcut {
+    TogglePlay,
+    StepBack,
+    StepForward,
+    Faster,
+    Slower,
+    Generate,
+    Reset,
+    WallMode,
+    StartMode,
+    EndMode,
+}
+
+impl Shortcut {
+    fn from_key(key: &str) -> Option<Self> {
+        Some(match key {
+            " " => Shortcut::TogglePlay,
+            "ArrowLeft" => Shortcut::StepBack,
+            "ArrowRight" => Shortcut::StepForward,
+            // `=` shares its key with `+` on most layouts
+            "+" | "=" => Shortcut::Faster,
+            "-" => Shortcut::Slower,
+            "g" | "G" => Shortcut::Generate,
+            "r" | "R" => Shortcut::Reset,
+            "w" | "W" => Shortcut::WallMode,
+            "s" | "S" => Shortcut::StartMode,
+            "e" | "E" => Shortcut::EndMode,
+            _ => return None,
+        })
+    }
+}
+
+impl Playback {
+    /// Carries out the shortcuts that only move playback. Returns `false`
+    /// for the ones left to the page.
+    pub fn handle(&self, shortcut: Shortcut) -> bool {
+        match shortcut {
+            Shortcut::TogglePlay => self.toggle(),
+            Shortcut::StepBack => self.back(),
+            Shortcut::StepForward => {
+                self.pause();
+                self.forward(1);
+            }
+            Shortcut::Faster => self.faster(),
+            Shortcut::Slower => self.slower(),
+            _ => return false,
+        }
+        true
+    }
+}
+
+impl SortRun {
+    /// Like [`Playback::handle`], except that space starts a sort when none
+    /// was recorded yet and `r` goes back to the unsorted array.
+    pub fn handle(&self, shortcut: Shortcut) -> bool {
+        match shortcut {
+            Shortcut::TogglePlay if self.playback.last.get_untracked() == 0 => self.start(),
+            Shortcut::Reset => self.reset(),
+            _ => return self.playback.handle(shortcut),
+        }
+        true
+    }
+}
+
+/// Calls `on_shortcut` for every shortcut pressed while the calling
+/// component is mounted. Keys typed into form fields or held with a
+/// modifier are left alone.
+pub fn use_shortcuts(on_shortcut: impl Fn(Shortcut) + 'static) {
+    let handle = window_event_listener(ev::keydown, move |ev| {
+        if ev.ctrl_key() || ev.meta_key() || ev.alt_key() || is_typing(&ev) {
+            return;
+        }
+        if let Some(shortcut) = Shortcut::from_key(&ev.key()) {
+            // keeps space and the arrows from scrolling the page
+            ev.prevent_default();
+            on_shortcut(shortcut);
+        }
+    });
+    on_cleanup(move || handle.remove());
+}
+
+/// Sliders and checkboxes take no text, so shortcuts still work after
+/// clicking one.
+fn is_typing(ev: &ev::KeyboardEvent) -> bool {
+    let Some(element) = ev.target().and_then(|target| target.dyn_into::<web_sys::Element>().ok()) else {
+        return false;
+    };
+    match element.dyn_ref::<web_sys::HtmlInputElement>() {
+        Some(input) => !matches!(input.type_().as_str(), "range" | "checkbox" | "button" | "submit"),
+        None => matches!(element.tag_name().as_str(), "SELECT" | "TEXTAREA"),
+    }
+}
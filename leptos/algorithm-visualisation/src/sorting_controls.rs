@@ -1,5 +1,6 @@
 use ev::{Event, MouseEvent};
 use leptos::*;
+use crate::seed::SeedInput;
 
 #[component]
 pub fn SortingControls(
@@ -8,6 +9,8 @@ pub fn SortingControls(
     #[prop(into)] on_generate: Callback<MouseEvent>,
     #[prop(into)] on_sort: Callback<MouseEvent>,
     #[prop(into)] on_size_change: Callback<Event>,
+    #[prop(into)] seed: Signal<u64>,
+    #[prop(into)] on_seed_change: Callback<u64>,
     #[prop(default = 10)] min_size: usize,
     #[prop(default = 150)] max_size: usize,
     #[prop(optional)] children: Option<Children>,
@@ -20,6 +23,7 @@ pub fn SortingControls(
                         class="px-4 py-2 bg-blue-500 text-white rounded hover:bg-blue-600 transition-colors"
                         on:click=on_generate
                         disabled=is_sorting
+                        title="Generate New Array (G)"
                     >
                         "Generate New Array"
                     </button>
@@ -27,6 +31,7 @@ pub fn SortingControls(
                         class="px-4 py-2 bg-green-500 text-white rounded hover:bg-green-600 transition-colors"
                         on:click=on_sort
                         disabled=is_sorting
+                        title="Start Sorting (space)"
                     >
                         "Start Sorting"
                     </button>
@@ -43,6 +48,7 @@ pub fn SortingControls(
                         on:input=on_size_change
                         disabled=is_sorting
                     />
+                    <SeedInput seed=seed on_seed_change=on_seed_change disabled=is_sorting/>
                 </div>
             </div>
         </div>
@@ -1,78 +1,30 @@
+use algoviz_core::catalog::AlgorithmInfo;
 use leptos::*;
 
-#[derive(Clone)]
-pub struct AlgorithmInfo {
-    pub name: &'static str,
-    pub description: &'static str,
-    pub best_time: &'static str,
-    pub average_time: &'static str,
-    pub worst_time: &'static str,
-    pub space: &'static str,
-    pub applications: &'static str,
-}
-
-pub const BUBBLE_SORT_INFO: AlgorithmInfo = AlgorithmInfo {
-    name: "Bubble Sort",
-    description: "Bubble Sort is a simple sorting algorithm that repeatedly steps through the list, compares adjacent elements and swaps them if they are in the wrong order. The algorithm gets its name because smaller elements 'bubble' to the top of the list with each iteration. This algorithm is mainly used for educational purposes and is practical only for small data sets where simple implementation is more important than efficiency. Its straightforward nature makes it an excellent teaching tool for introducing the concept of sorting algorithms.",
-    best_time: "O(n)",
-    average_time: "O(n²)",
-    worst_time: "O(n²)",
-    space: "O(1)",
-    applications: "Educational purposes and small datasets",
-};
-
-pub const SELECTION_SORT_INFO: AlgorithmInfo = AlgorithmInfo {
-    name: "Selection Sort",
-    description: "Selection Sort divides the input into a sorted and an unsorted region. It repeatedly finds the minimum element from the unsorted region and adds it to the end of the sorted region. Like Bubble Sort, it's primarily used for educational purposes and small datasets where minimizing the number of swaps is important. It performs the same number of comparisons regardless of the initial order of elements, making it inefficient for large datasets.",
-    best_time: "O(n²)",
-    average_time: "O(n²)",
-    worst_time: "O(n²)",
-    space: "O(1)",
-    applications: "Small datasets and educational purposes",
-};
-
-pub const INSERTION_SORT_INFO: AlgorithmInfo = AlgorithmInfo {
-    name: "Insertion Sort",
-    description: "Insertion Sort builds the final sorted array one item at a time by iterating through the input array and shifting larger elements to the right to make room for the current element being inserted. It's highly efficient for small and nearly sorted arrays. The algorithm's performance significantly improves when dealing with partially sorted data, making it a practical choice for maintaining sorted lists or sorting small chunks of data within larger algorithms.",
-    best_time: "O(n)",
-    average_time: "O(n²)",
-    worst_time: "O(n²)",
-    space: "O(1)",
-    applications: "Small datasets and nearly sorted arrays",
-};
-
-pub const QUICK_SORT_INFO: AlgorithmInfo = AlgorithmInfo {
-    name: "Quick Sort",
-    description: "Quick Sort is based on the divide-and-conquer strategy. It selects a 'pivot' element and partitions the array around it, with smaller elements going to one side and larger elements to the other. The pivot then goes to its final position, and the process is recursively repeated for both sub-arrays. This algorithm is widely used in practice due to its efficient average-case performance and in-place sorting capability.",
-    best_time: "O(n log n)",
-    average_time: "O(n log n)",
-    worst_time: "O(n²)",
-    space: "O(log n)",
-    applications: "General-purpose sorting, standard library implementations",
-};
-
-pub const MERGE_SORT_INFO: AlgorithmInfo = AlgorithmInfo {
-    name: "Merge Sort",
-    description: "Merge Sort divides the array into halves down to single elements, then merges these parts back together in sorted order. During merging, it compares elements from both parts and combines them in order, creating increasingly larger sorted segments. It guarantees consistent performance regardless of input order and is particularly efficient for large datasets. The algorithm is stable and predictable but requires additional memory space.",
-    best_time: "O(n log n)",
-    average_time: "O(n log n)",
-    worst_time: "O(n log n)",
-    space: "O(n)",
-    applications: "Large datasets, external sorting, stable sorting requirements",
-};
-
+/// Bogo sort only exists to be watched, so unlike the algorithms in the shared
+/// core its description lives here.
 pub const BOGO_SORT_INFO: AlgorithmInfo = AlgorithmInfo {
+    slug: "bogo",
     name: "Bogo Sort",
     description: "Bogo Sort is a highly inefficient sorting algorithm that works by randomly shuffling elements and checking if they are sorted. If not, it repeats the process. It serves as an educational example of how not to design algorithms. While there's always a minimal chance it might sort correctly on the first shuffle, it's completely impractical for any real use. Its unpredictable runtime and potential to never terminate make it a perfect example of an inefficient algorithm.",
     best_time: "O(n)",
     average_time: "O(n × n!)",
     worst_time: "∞",
     space: "O(1)",
+    stable: false,
+    in_place: true,
+    adaptive: false,
     applications: "Educational purposes only - demonstrating how not to design algorithms",
+    pseudocode: &["while a is not sorted:", "    shuffle a"],
 };
 
+/// Shows `algorithm_info` and its pseudocode, highlighting `active_line`
+/// while a run plays.
 #[component]
-pub fn AlgorithmInfoPanel(algorithm_info: AlgorithmInfo) -> impl IntoView {
+pub fn AlgorithmInfoPanel(
+    algorithm_info: AlgorithmInfo,
+    #[prop(optional, into)] active_line: MaybeSignal<Option<usize>>,
+) -> impl IntoView {
     view! {
         <div class="w-full max-w-7xl mx-auto p-4 mt-4">
             <div class="bg-white rounded-lg shadow-md p-6">
@@ -87,6 +39,10 @@ pub fn AlgorithmInfoPanel(algorithm_info: AlgorithmInfo) -> impl IntoView {
                             <h4 class="font-semibold mb-2">"Common Applications:"</h4>
                             <p class="text-gray-700">{algorithm_info.applications}</p>
                         </div>
+                        <div class="mt-4">
+                            <h4 class="font-semibold mb-2">"Pseudocode"</h4>
+                            <Pseudocode code=algorithm_info.pseudocode active_line=active_line/>
+                        </div>
                     </div>
                     
                     // Complexity section
@@ -102,13 +58,50 @@ pub fn AlgorithmInfoPanel(algorithm_info: AlgorithmInfo) -> impl IntoView {
                             </ul>
                         </div>
                         
-                        <div>
+                        <div class="mb-4">
                             <h4 class="font-semibold mb-2">"Space Complexity"</h4>
                             <p>{algorithm_info.space}</p>
                         </div>
+
+                        <div>
+                            <h4 class="font-semibold mb-2">"Properties"</h4>
+                            <ul class="space-y-2">
+                                <li>"Stable: " {yes_no(algorithm_info.stable)}</li>
+                                <li>"In-place: " {yes_no(algorithm_info.in_place)}</li>
+                                <li>"Adaptive: " {yes_no(algorithm_info.adaptive)}</li>
+                            </ul>
+                        </div>
                     </div>
                 </div>
             </div>
         </div>
     }
-}
\ No newline at end of file
+}
+
+#[component]
+pub fn Pseudocode(code: &'static [&'static str], #[prop(into)] active_line: MaybeSignal<Option<usize>>) -> impl IntoView {
+    view! {
+        <pre class="bg-gray-900 text-gray-100 text-sm rounded-lg p-4 overflow-x-auto">
+            {code
+                .iter()
+                .enumerate()
+                .map(|(number, line)| {
+                    let active = move || active_line.get() == Some(number);
+                    view! {
+                        <div
+                            class="px-2 rounded transition-colors"
+                            class=("bg-green-600", active)
+                        >
+                            <span class="select-none text-gray-500 mr-4">{format!("{:>2}", number + 1)}</span>
+                            {*line}
+                        </div>
+                    }
+                })
+                .collect_view()}
+        </pre>
+    }
+}
+
+fn yes_no(flag: bool) -> &'static str {
+    if flag { "Yes" } else { "No" }
+}
@@ -0,0 +1,106 @@
+use rocket::http::{ContentType, Header};
+use rocket::State;
+use algoviz_core::animation::{self, AnimationFormat, AnimationOptions};
+use algoviz_core::snapshot::bars_svg;
+use algoviz_core::steps::Steps;
+
+use crate::error::ApiError;
+use crate::limits::{run_limited, Guarded, SortLimits};
+use crate::routes::parse_algorithm;
+use crate::sorting::sort;
+
+const MIN_FRAME_MS: u32 = 10;
+const MAX_FRAME_MS: u32 = 10_000;
+const MAX_WIDTH: u16 = 1920;
+const MAX_HEIGHT: u16 = 1080;
+const MIN_SIDE: u16 = 50;
+
+/// A rendered animation, offered as a download named after the algorithm.
+#[derive(Responder)]
+pub struct Animation {
+    body: Vec<u8>,
+    content_type: ContentType,
+    disposition: Header<'static>,
+}
+
+/// Renders the run as a looping SVG or GIF, one frame per chart step.
+/// Sizes and frame times outside the supported range are clamped.
+#[get("/<algorithm_type>/animation?<numbers>&<format>&<frame_ms>&<width>&<height>")]
+pub async fn animate_sort(
+    algorithm_type: &str,
+    numbers: Vec<i64>,
+    format: Option<&str>,
+    frame_ms: Option<u32>,
+    width: Option<u16>,
+    height: Option<u16>,
+    limits: &State<SortLimits>,
+) -> Result<Animation, ApiError> {
+    let algorithm = parse_algorithm(algorithm_type)?;
+    let format: AnimationFormat = format
+        .unwrap_or("gif")
+        .parse()
+        .map_err(|reason: &'static str| ApiError::UnprocessableEntity(reason.into()))?;
+    limits.check_numbers(algorithm, &numbers)?;
+    let defaults = AnimationOptions::default();
+    let options = AnimationOptions {
+        width: width.unwrap_or(defaults.width).clamp(MIN_SIDE, MAX_WIDTH),
+        height: height.unwrap_or(defaults.height).clamp(MIN_SIDE, MAX_HEIGHT),
+        frame_ms: frame_ms.unwrap_or(defaults.frame_ms).clamp(MIN_FRAME_MS, MAX_FRAME_MS),
+    };
+
+    let max_steps = limits.max_animation_steps;
+    let recorder = Guarded::new(Vec::new(), limits.deadline(), Some(limits.max_operations(algorithm)));
+    let body = run_limited(move || {
+        let operations = sort(algorithm, numbers.clone(), recorder).operations.into_inner();
+        let steps = Steps::count(&operations);
+        if steps > max_steps {
+            return Err(ApiError::PayloadTooLarge(
+                format!("The animation would have {steps} steps, more than the {max_steps} allowed; try a smaller array.").into(),
+            ));
+        }
+        Ok(animation::render(format, &Steps::from_trace(algorithm, numbers, &operations), options))
+    })
+    .await??;
+
+    let content_type = match format {
+        AnimationFormat::Svg => ContentType::SVG,
+        AnimationFormat::Gif => ContentType::GIF,
+    };
+    let file_name = format!("{}-sort.{}", algorithm.slug(), format.extension());
+    Ok(Animation {
+        body,
+        content_type,
+        disposition: Header::new("Content-Disposition", format!("attachment; filename=\"{file_name}\"")),
+    })
+}
+
+/// One step of the run as a still SVG bar chart, counted as in the
+/// animations: step 0 is the input and the last step, drawn when `step` is
+/// left out, the sorted array.
+#[get("/<algorithm_type>/snapshot?<numbers>&<step>&<width>&<height>")]
+pub async fn snapshot_sort(
+    algorithm_type: &str,
+    numbers: Vec<i64>,
+    step: Option<usize>,
+    width: Option<u16>,
+    height: Option<u16>,
+    limits: &State<SortLimits>,
+) -> Result<(ContentType, String), ApiError> {
+    let algorithm = parse_algorithm(algorithm_type)?;
+    limits.check_numbers(algorithm, &numbers)?;
+    let defaults = AnimationOptions::default();
+    let width = width.unwrap_or(defaults.width).clamp(MIN_SIDE, MAX_WIDTH);
+    let height = height.unwrap_or(defaults.height).clamp(MIN_SIDE, MAX_HEIGHT);
+
+    let recorder = Guarded::new(Vec::new(), limits.deadline(), Some(limits.max_operations(algorithm)));
+    let svg = run_limited(move || -> Result<String, ApiError> {
+        let operations = sort(algorithm, numbers.clone(), recorder).operations.into_inner();
+        let last = Steps::count(&operations) - 1;
+        let step = step.unwrap_or(last);
+        let (values, colors) = Steps::step(algorithm, numbers, &operations, step)
+            .ok_or_else(|| ApiError::UnprocessableEntity(format!("Step {step} is past the last step, {last}.").into()))?;
+        Ok(bars_svg(&values, &colors, width, height))
+    })
+    .await??;
+    Ok((ContentType::SVG, svg))
+}
@@ -0,0 +1,241 @@
+use std::time::{Duration, Instant};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use rocket::serde::{Deserialize, Serialize, json::{Json, json}};
+use rocket::State;
+use algoviz_core::sorting::AlgorithmType;
+use algoviz_core::trace::{Discard, Metrics};
+
+use crate::error::{ApiError, ApiResult};
+use crate::inputs::{generate, Distribution, DistributionOptions};
+use crate::limits::{run_limited, Guarded, SortLimits};
+use crate::sorting::sort;
+
+const DEFAULT_BENCHMARK_RUNS: u32 = 3;
+const MAX_BENCHMARK_RUNS: u32 = 100;
+
+/// Spread of wall-clock durations over repeated uninstrumented runs.
+#[derive(Serialize, Debug)]
+#[serde(crate = "rocket::serde")]
+pub struct Timing {
+    pub runs: u32,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+/// Sorts a fresh copy of `numbers` `runs` times without recording any
+/// operations, so the durations reflect the algorithm rather than tracing.
+/// Each run only counts its operations, looking at the clock every few
+/// thousand of them, so a slow run is stopped at `deadline` too.
+pub fn time_sort(algorithm: AlgorithmType, numbers: &[i64], runs: u32, deadline: Instant) -> Timing {
+    let mut durations: Vec<Duration> = (0..runs)
+        .map(|_| sort(algorithm, numbers.to_vec(), Guarded::new(Discard, deadline, None)).duration)
+        .collect();
+    durations.sort();
+
+    let middle = durations.len() / 2;
+    let median = if durations.len().is_multiple_of(2) {
+        (durations[middle - 1] + durations[middle]) / 2
+    } else {
+        durations[middle]
+    };
+    let mean = durations.iter().sum::<Duration>() / runs;
+    let variance = durations
+        .iter()
+        .map(|duration| (duration.as_secs_f64() - mean.as_secs_f64()).powi(2))
+        .sum::<f64>() / f64::from(runs);
+
+    Timing {
+        runs,
+        min: durations[0],
+        median,
+        mean,
+        stddev: Duration::from_secs_f64(variance.sqrt()),
+    }
+}
+
+/// Reference growth rates a measured series is matched against.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(crate = "rocket::serde")]
+pub enum Complexity {
+    #[serde(rename = "n")]
+    Linear,
+    #[serde(rename = "n log n")]
+    Linearithmic,
+    #[serde(rename = "n^2")]
+    Quadratic,
+}
+
+impl Complexity {
+    const ALL: [Complexity; 3] = [Complexity::Linear, Complexity::Linearithmic, Complexity::Quadratic];
+
+    fn apply(self, n: f64) -> f64 {
+        match self {
+            Complexity::Linear => n,
+            Complexity::Linearithmic => n * n.log2(),
+            Complexity::Quadratic => n * n,
+        }
+    }
+}
+
+/// How a series of `(size, cost)` samples grows with the input size.
+#[derive(Serialize, Debug)]
+#[serde(crate = "rocket::serde")]
+pub struct Fit {
+    /// Slope of the least-squares line through `ln cost` against `ln size`;
+    /// roughly 1 for linear and 2 for quadratic growth.
+    pub log_log_slope: Option<f64>,
+    /// Reference curve whose ratio to the samples varies the least.
+    pub best_match: Option<Complexity>,
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn fit(samples: &[(usize, f64)]) -> Fit {
+    let usable: Vec<(f64, f64)> = samples
+        .iter()
+        .filter(|&&(size, cost)| size > 1 && cost > 0.0)
+        .map(|&(size, cost)| (size as f64, cost))
+        .collect();
+    if usable.len() < 2 {
+        return Fit { log_log_slope: None, best_match: None };
+    }
+
+    let count = usable.len() as f64;
+    let logs: Vec<(f64, f64)> = usable.iter().map(|&(n, cost)| (n.ln(), cost.ln())).collect();
+    let mean_x = logs.iter().map(|&(x, _)| x).sum::<f64>() / count;
+    let mean_y = logs.iter().map(|&(_, y)| y).sum::<f64>() / count;
+    let covariance: f64 = logs.iter().map(|&(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let spread: f64 = logs.iter().map(|&(x, _)| (x - mean_x).powi(2)).sum();
+    let log_log_slope = (spread > 0.0).then(|| covariance / spread);
+
+    // coefficient of variation of cost / f(n): a perfect match has a constant ratio
+    let variation = |complexity: Complexity| {
+        let ratios: Vec<f64> = usable.iter().map(|&(n, cost)| cost / complexity.apply(n)).collect();
+        let mean = ratios.iter().sum::<f64>() / count;
+        let variance = ratios.iter().map(|ratio| (ratio - mean).powi(2)).sum::<f64>() / count;
+        variance.sqrt() / mean
+    };
+    let best_match = Complexity::ALL
+        .into_iter()
+        .min_by(|&a, &b| variation(a).total_cmp(&variation(b)));
+
+    Fit { log_log_slope, best_match }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(crate = "rocket::serde")]
+pub struct BenchmarkRequest {
+    algorithms: Vec<AlgorithmType>,
+    sizes: Vec<usize>,
+    #[serde(default = "default_distributions")]
+    distributions: Vec<Distribution>,
+    runs: Option<u32>,
+    seed: Option<u64>,
+}
+
+fn default_distributions() -> Vec<Distribution> {
+    vec![Distribution::Random]
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "rocket::serde")]
+pub struct BenchmarkPoint {
+    pub size: usize,
+    pub metrics: Metrics,
+    pub timing: Timing,
+}
+
+/// One algorithm on one distribution across every requested size.
+#[derive(Serialize, Debug)]
+#[serde(crate = "rocket::serde")]
+pub struct BenchmarkSeries {
+    pub algorithm: AlgorithmType,
+    pub distribution: Distribution,
+    pub points: Vec<BenchmarkPoint>,
+    /// Fit of the comparison counts.
+    pub operations_fit: Fit,
+    /// Fit of the median durations.
+    pub timing_fit: Fit,
+}
+
+/// Every series restarts from `seed`, so all algorithms see the same arrays.
+/// Sizes beyond `max_length` are left out of the series.
+#[allow(clippy::cast_precision_loss)]
+fn run_series(
+    algorithm: AlgorithmType,
+    distribution: Distribution,
+    sizes: &[usize],
+    max_length: usize,
+    runs: u32,
+    seed: u64,
+    deadline: Instant,
+) -> BenchmarkSeries {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let points: Vec<BenchmarkPoint> = sizes
+        .iter()
+        .take_while(|&&size| size <= max_length)
+        .map(|&size| {
+            let numbers = generate(distribution, size, DistributionOptions::default(), &mut rng);
+            let metrics = sort(algorithm, numbers.clone(), Guarded::new(Discard, deadline, None)).metrics;
+            let timing = time_sort(algorithm, &numbers, runs, deadline);
+            BenchmarkPoint { size, metrics, timing }
+        })
+        .collect();
+    let operations: Vec<(usize, f64)> = points
+        .iter()
+        .map(|point| (point.size, point.metrics.comparisons as f64))
+        .collect();
+    let timings: Vec<(usize, f64)> = points
+        .iter()
+        .map(|point| (point.size, point.timing.median.as_secs_f64()))
+        .collect();
+    BenchmarkSeries {
+        algorithm,
+        distribution,
+        operations_fit: fit(&operations),
+        timing_fit: fit(&timings),
+        points,
+    }
+}
+
+/// Runs every requested algorithm over every size and distribution and fits
+/// the growth of both comparison counts and durations. Each algorithm only
+/// runs the sizes within its length limit, so the quadratic sorts stop short
+/// of the others. The seed used for the inputs is returned so the run can be
+/// repeated.
+#[post("/benchmark", format = "json", data = "<benchmark_request>")]
+pub async fn benchmark(benchmark_request: Json<BenchmarkRequest>, limits: &State<SortLimits>) -> ApiResult {
+    let BenchmarkRequest { algorithms, mut sizes, distributions, runs, seed } = benchmark_request.into_inner();
+    if algorithms.is_empty() || sizes.is_empty() || distributions.is_empty() {
+        return Err(ApiError::UnprocessableEntity("Algorithms, sizes and distributions must not be empty.".into()));
+    }
+    let runs = runs.unwrap_or(DEFAULT_BENCHMARK_RUNS).clamp(1, MAX_BENCHMARK_RUNS);
+    let seed = seed.unwrap_or_else(|| rand::thread_rng().gen());
+    sizes.sort_unstable();
+    sizes.dedup();
+    // every algorithm needs at least one size to fit
+    let smallest = sizes[0];
+    let algorithms = algorithms
+        .into_iter()
+        .map(|algorithm| limits.check_length(algorithm, smallest).map(|()| (algorithm, limits.max_length(algorithm))))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let deadline = limits.deadline();
+    let series = run_limited(move || {
+        algorithms
+            .iter()
+            .flat_map(|&algorithm| distributions.iter().map(move |&distribution| (algorithm, distribution)))
+            .map(|((algorithm, max_length), distribution)| {
+                run_series(algorithm, distribution, &sizes, max_length, runs, seed, deadline)
+            })
+            .collect::<Vec<_>>()
+    })
+    .await?;
+
+    Ok(Json(json!({
+        "status": "success",
+        "seed": seed,
+        "series": series,
+    })))
+}
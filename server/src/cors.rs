@@ -1,6 +1,44 @@
 use rocket::fairing::{Fairing, Info, Kind};
 use rocket::http::{Header, Method, Status};
-use rocket::{Request, Response};
+use rocket::serde::Deserialize;
+use rocket::{Build, Request, Response, Rocket};
+
+/// CORS settings, read from the `cors` table of `Rocket.toml` or from
+/// `ROCKET_CORS`, e.g. `ROCKET_CORS='{allowed_origins=["https://example.com"]}'`.
+#[derive(Deserialize, Debug)]
+#[serde(crate = "rocket::serde", default)]
+pub struct CorsConfig {
+    /// Exact origins allowed to call the API; `"*"` allows any origin, which
+    /// is refused together with `allow_credentials`.
+    pub allowed_origins: Vec<String>,
+    pub allowed_methods: Vec<String>,
+    pub allow_credentials: bool,
+    /// Seconds a browser may cache a preflight answer.
+    pub max_age: Option<u32>,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        CorsConfig {
+            allowed_origins: vec!["http://localhost:3000".to_string()],
+            allowed_methods: ["GET", "POST", "PATCH", "DELETE"].map(String::from).to_vec(),
+            allow_credentials: true,
+            max_age: None,
+        }
+    }
+}
+
+impl CorsConfig {
+    fn allows(&self, origin: &str) -> bool {
+        self.allowed_origins.iter().any(|allowed| allowed == "*" || allowed == origin)
+    }
+}
+
+/// Answers preflight requests for every path; the headers are added by [`CORS`].
+#[options("/<_..>")]
+pub fn preflight() -> Status {
+    Status::NoContent
+}
 
 pub struct CORS;
 
@@ -9,24 +47,53 @@ impl Fairing for CORS {
     fn info(&self) -> Info {
         Info {
             name: "Add CORS headers to responses",
-            kind: Kind::Response,
+            kind: Kind::Ignite | Kind::Response,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> rocket::fairing::Result {
+        let config = match rocket.figment().extract_inner::<CorsConfig>("cors") {
+            Ok(config) => config,
+            Err(err) if err.missing() => CorsConfig::default(),
+            Err(err) => {
+                error!("Invalid CORS configuration: {err}");
+                return Err(rocket);
+            }
+        };
+        if config.allow_credentials && config.allowed_origins.iter().any(|allowed| allowed == "*") {
+            // echoing any origin with credentials would let every site call the API as the user
+            error!("Invalid CORS configuration: allowed_origins \"*\" cannot be combined with allow_credentials.");
+            return Err(rocket);
         }
+        Ok(rocket.manage(config).mount("/", routes![preflight]))
     }
 
     async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
-        if request.method() == Method::Options {
-            response.set_status(Status::NoContent);
-            response.set_header(Header::new(
-                "Access-Control-Allow-Methods",
-                "POST, PATCH, GET, DELETE",
-            ));
-            response.set_header(Header::new("Access-Control-Allow-Headers", "*"));
+        let Some(config) = request.rocket().state::<CorsConfig>() else {
+            return;
+        };
+        // the allowed origin depends on the request, so caches must key on it
+        response.adjoin_header(Header::new("Vary", "Origin"));
+        let Some(origin) = request.headers().get_one("Origin") else {
+            return;
+        };
+        if !config.allows(origin) {
+            return;
         }
 
-        response.set_header(Header::new(
-            "Access-Control-Allow-Origin",
-            "http://localhost:3000",
-        ));
-        response.set_header(Header::new("Access-Control-Allow-Credentials", "true"));
+        response.set_header(Header::new("Access-Control-Allow-Origin", origin.to_string()));
+        if config.allow_credentials {
+            response.set_header(Header::new("Access-Control-Allow-Credentials", "true"));
+        }
+        let is_preflight = request.method() == Method::Options
+            && request.headers().contains("Access-Control-Request-Method");
+        if is_preflight {
+            response.set_header(Header::new("Access-Control-Allow-Methods", config.allowed_methods.join(", ")));
+            let headers = request.headers().get_one("Access-Control-Request-Headers").unwrap_or("Content-Type");
+            response.set_header(Header::new("Access-Control-Allow-Headers", headers.to_string()));
+            if let Some(max_age) = config.max_age {
+                response.set_header(Header::new("Access-Control-Max-Age", max_age.to_string()));
+            }
+        }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,91 @@
+use std::borrow::Cow;
+use rocket::http::Status;
+use rocket::response::{self, Responder};
+use rocket::serde::json::{Json, Value, json};
+use rocket::Request;
+
+/// Every way a request can fail. Responds with the matching status code and
+/// a body of the form `{"status": "error", "code": 422, "reason": "..."}`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApiError {
+    /// The request could not be read, e.g. malformed JSON or query values.
+    BadRequest(Cow<'static, str>),
+    /// Unknown route, or an algorithm or distribution that does not exist.
+    NotFound(Cow<'static, str>),
+    /// The sort did not finish within the configured time.
+    RequestTimeout(Cow<'static, str>),
+    /// The input is larger than the server accepts.
+    PayloadTooLarge(Cow<'static, str>),
+    /// The request is well-formed but its values make no sense, e.g. an empty array.
+    UnprocessableEntity(Cow<'static, str>),
+    Internal(Cow<'static, str>),
+}
+
+pub type ApiResult<T = Value> = Result<Json<T>, ApiError>;
+
+impl ApiError {
+    pub fn status(&self) -> Status {
+        match self {
+            ApiError::BadRequest(_) => Status::BadRequest,
+            ApiError::NotFound(_) => Status::NotFound,
+            ApiError::RequestTimeout(_) => Status::RequestTimeout,
+            ApiError::PayloadTooLarge(_) => Status::PayloadTooLarge,
+            ApiError::UnprocessableEntity(_) => Status::UnprocessableEntity,
+            ApiError::Internal(_) => Status::InternalServerError,
+        }
+    }
+
+    pub fn reason(&self) -> &str {
+        match self {
+            ApiError::BadRequest(reason)
+            | ApiError::NotFound(reason)
+            | ApiError::RequestTimeout(reason)
+            | ApiError::PayloadTooLarge(reason)
+            | ApiError::UnprocessableEntity(reason)
+            | ApiError::Internal(reason) => reason,
+        }
+    }
+
+    pub fn body(&self) -> Value {
+        json!({
+            "status": "error",
+            "code": self.status().code,
+            "reason": self.reason(),
+        })
+    }
+}
+
+impl<'r> Responder<'r, 'static> for ApiError {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        (self.status(), Json(self.body())).respond_to(request)
+    }
+}
+
+#[catch(400)]
+fn bad_request() -> ApiError {
+    ApiError::BadRequest("Request could not be read; check that the body is valid JSON.".into())
+}
+
+#[catch(404)]
+fn not_found() -> ApiError {
+    ApiError::NotFound("Resource was not found.".into())
+}
+
+#[catch(413)]
+fn payload_too_large() -> ApiError {
+    ApiError::PayloadTooLarge("Request body is too large.".into())
+}
+
+#[catch(422)]
+fn unprocessable_entity() -> ApiError {
+    ApiError::UnprocessableEntity("Request body or query parameters have the wrong fields or types.".into())
+}
+
+#[catch(500)]
+fn internal_error() -> ApiError {
+    ApiError::Internal("Internal server error.".into())
+}
+
+pub fn catchers() -> Vec<rocket::Catcher> {
+    catchers![bad_request, not_found, payload_too_large, unprocessable_entity, internal_error]
+}
@@ -0,0 +1,143 @@
+use std::f64::consts::TAU;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use rocket::request::FromParam;
+use rocket::serde::{Deserialize, Serialize, json::{Json, json}};
+
+use crate::error::{ApiError, ApiResult};
+
+const MAX_GENERATED_SIZE: usize = 100_000;
+
+/// Shape of a generated input array.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(crate = "rocket::serde", rename_all = "snake_case")]
+pub enum Distribution {
+    Random,
+    Sorted,
+    Reversed,
+    NearlySorted,
+    FewUnique,
+    OrganPipe,
+    Sawtooth,
+    Gaussian,
+    AllEqual,
+}
+
+impl<'r> FromParam<'r> for Distribution {
+    type Error = &'static str;
+
+    fn from_param(param: &'r str) -> Result<Self, Self::Error> {
+        match param {
+            "random" => Ok(Distribution::Random),
+            "sorted" => Ok(Distribution::Sorted),
+            "reversed" => Ok(Distribution::Reversed),
+            "nearly_sorted" => Ok(Distribution::NearlySorted),
+            "few_unique" => Ok(Distribution::FewUnique),
+            "organ_pipe" => Ok(Distribution::OrganPipe),
+            "sawtooth" => Ok(Distribution::Sawtooth),
+            "gaussian" => Ok(Distribution::Gaussian),
+            "all_equal" => Ok(Distribution::AllEqual),
+            _ => Err("Invalid distribution."),
+        }
+    }
+}
+
+/// Knobs for the distributions that take one; anything left out falls back
+/// to a value scaled to the array size.
+#[derive(Serialize, Deserialize, FromForm, Debug, Clone, Copy, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct DistributionOptions {
+    /// Random swaps applied to a sorted array for `nearly_sorted`, at most
+    /// the array size.
+    pub swaps: Option<usize>,
+    /// Distinct values used by `few_unique`.
+    pub unique: Option<usize>,
+    /// Number of ramps in `sawtooth`.
+    pub teeth: Option<usize>,
+}
+
+impl DistributionOptions {
+    /// Rejects knobs that would make generation run far longer than the
+    /// array is big.
+    fn check(&self, size: usize) -> Result<(), ApiError> {
+        match self.swaps {
+            Some(swaps) if swaps > size => Err(ApiError::UnprocessableEntity("Swaps must not exceed the size.".into())),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Builds `size` values in `1..=size` following `distribution`.
+pub fn generate<G: Rng>(distribution: Distribution, size: usize, options: DistributionOptions, rng: &mut G) -> Vec<i64> {
+    let max = i64::try_from(size).unwrap_or(i64::MAX);
+    let ascending: Vec<i64> = (1..=max).collect();
+    match distribution {
+        Distribution::Random => (0..size).map(|_| rng.gen_range(1..=max)).collect(),
+        Distribution::Sorted => ascending,
+        Distribution::Reversed => ascending.into_iter().rev().collect(),
+        Distribution::NearlySorted => {
+            let mut values = ascending;
+            if size > 1 {
+                for _ in 0..options.swaps.unwrap_or(size / 20 + 1) {
+                    let i = rng.gen_range(0..size);
+                    let j = rng.gen_range(0..size);
+                    values.swap(i, j);
+                }
+            }
+            values
+        }
+        Distribution::FewUnique => {
+            let unique = options.unique.unwrap_or(5).clamp(1, size.max(1));
+            // spread the distinct values evenly over 1..=size
+            let levels: Vec<i64> = (1..=unique)
+                .map(|level| i64::try_from(level * size / unique).unwrap_or(max))
+                .collect();
+            (0..size).map(|_| levels[rng.gen_range(0..unique)]).collect()
+        }
+        Distribution::OrganPipe => {
+            let half = size.div_ceil(2);
+            (0..size)
+                .map(|index| if index < half { index } else { size - 1 - index })
+                .map(|rise| i64::try_from(2 * rise + 1).unwrap_or(max))
+                .collect()
+        }
+        Distribution::Sawtooth => {
+            let teeth = options.teeth.unwrap_or(4).clamp(1, size.max(1));
+            let width = size.div_ceil(teeth);
+            (0..size)
+                .map(|index| i64::try_from((index % width + 1) * size / width).unwrap_or(max))
+                .collect()
+        }
+        Distribution::Gaussian => {
+            #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+            let sample = |rng: &mut G| {
+                // Box-Muller transform around the middle of the range
+                let (u1, u2): (f64, f64) = (rng.gen_range(f64::EPSILON..1.0), rng.gen());
+                let normal = (-2.0 * u1.ln()).sqrt() * (TAU * u2).cos();
+                let value = (size as f64 / 2.0 + normal * size as f64 / 6.0).round() as i64;
+                value.clamp(1, max)
+            };
+            (0..size).map(|_| sample(rng)).collect()
+        }
+        Distribution::AllEqual => vec![(max + 1) / 2; size],
+    }
+}
+
+/// Generates an input array. Without a `seed` a random one is picked and
+/// returned, so any array can be requested again.
+#[get("/inputs/<distribution>?<size>&<seed>&<options..>")]
+pub fn generate_input(distribution: Result<Distribution, &'static str>, size: usize, seed: Option<u64>, options: DistributionOptions) -> ApiResult {
+    let distribution = distribution.map_err(|reason| ApiError::NotFound(reason.into()))?;
+    if size > MAX_GENERATED_SIZE {
+        return Err(ApiError::PayloadTooLarge(format!("Size must not exceed {MAX_GENERATED_SIZE}.").into()));
+    }
+    options.check(size)?;
+    let seed = seed.unwrap_or_else(|| rand::thread_rng().gen());
+    let numbers = generate(distribution, size, options, &mut StdRng::seed_from_u64(seed));
+    Ok(Json(json!({
+        "status": "success",
+        "distribution": distribution,
+        "size": size,
+        "seed": seed,
+        "numbers": numbers,
+    })))
+}
@@ -0,0 +1,175 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::future::Future;
+use std::panic::resume_unwind;
+use std::time::{Duration, Instant};
+use rocket::serde::Deserialize;
+use rocket::tokio::task::spawn_blocking;
+use algoviz_core::sorting::AlgorithmType;
+use algoviz_core::trace::{Operation, Recorder};
+
+use crate::error::ApiError;
+
+/// How many operations pass between two looks at the clock.
+const DEADLINE_CHECK_INTERVAL: usize = 4096;
+
+/// Caps on the work a single request may ask for, read from the `sorting`
+/// table of `Rocket.toml` or from `ROCKET_SORTING`.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(crate = "rocket::serde", default)]
+pub struct SortLimits {
+    /// Wall-clock budget for one request, in seconds.
+    pub timeout_secs: u64,
+    pub max_length: usize,
+    /// Memory the recorded operations of one run may take up.
+    pub max_history_bytes: usize,
+    /// Most steps an exported animation may show.
+    pub max_animation_steps: usize,
+    /// Tighter limits for individual algorithms, e.g. the quadratic ones.
+    pub algorithms: HashMap<AlgorithmType, AlgorithmLimits>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct AlgorithmLimits {
+    pub max_length: Option<usize>,
+    pub max_history_bytes: Option<usize>,
+}
+
+impl Default for SortLimits {
+    fn default() -> Self {
+        let quadratic = AlgorithmLimits { max_length: Some(1000), max_history_bytes: None };
+        SortLimits {
+            timeout_secs: 10,
+            max_length: 100_000,
+            max_history_bytes: 32 * 1024 * 1024,
+            max_animation_steps: 5000,
+            algorithms: HashMap::from([
+                (AlgorithmType::Selection, quadratic),
+                (AlgorithmType::Bubble, quadratic),
+                (AlgorithmType::Insertion, quadratic),
+                // quick sort turns quadratic on sorted input
+                (AlgorithmType::Quick, AlgorithmLimits { max_length: Some(10_000), max_history_bytes: None }),
+            ]),
+        }
+    }
+}
+
+impl SortLimits {
+    fn for_algorithm(&self, algorithm: AlgorithmType) -> AlgorithmLimits {
+        self.algorithms.get(&algorithm).copied().unwrap_or_default()
+    }
+
+    pub fn max_length(&self, algorithm: AlgorithmType) -> usize {
+        self.for_algorithm(algorithm).max_length.unwrap_or(self.max_length)
+    }
+
+    pub fn max_operations(&self, algorithm: AlgorithmType) -> usize {
+        let bytes = self.for_algorithm(algorithm).max_history_bytes.unwrap_or(self.max_history_bytes);
+        bytes / std::mem::size_of::<Operation>()
+    }
+
+    pub fn deadline(&self) -> Instant {
+        Instant::now() + Duration::from_secs(self.timeout_secs)
+    }
+
+    /// Rejects arrays longer than `algorithm` may sort.
+    pub fn check_length(&self, algorithm: AlgorithmType, length: usize) -> Result<(), ApiError> {
+        let max_length = self.max_length(algorithm);
+        if length > max_length {
+            return Err(ApiError::PayloadTooLarge(
+                format!("Arrays for {algorithm:?} sort are limited to {max_length} numbers.").into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Rejects arrays the sorts cannot run on or that are too long for `algorithm`.
+    pub fn check_numbers(&self, algorithm: AlgorithmType, numbers: &[i64]) -> Result<(), ApiError> {
+        if numbers.is_empty() {
+            return Err(ApiError::UnprocessableEntity("Numbers must not be empty.".into()));
+        }
+        self.check_length(algorithm, numbers.len())
+    }
+}
+
+/// Why a run was stopped before it finished.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Aborted {
+    Timeout,
+    HistoryTooLarge,
+}
+
+impl From<Aborted> for ApiError {
+    fn from(aborted: Aborted) -> Self {
+        match aborted {
+            Aborted::Timeout => ApiError::RequestTimeout("Sorting exceeded the time limit.".into()),
+            Aborted::HistoryTooLarge => ApiError::PayloadTooLarge(
+                "The recorded operations exceed the history limit; try a smaller array.".into(),
+            ),
+        }
+    }
+}
+
+/// Unwinds out of the running sort. Only valid inside [`run_limited`], which
+/// turns it back into an error.
+pub fn abort(reason: Aborted) -> ! {
+    // `resume_unwind` skips the panic hook, so nothing is logged as a crash
+    resume_unwind(Box::new(reason))
+}
+
+pub fn check_deadline(deadline: Instant) {
+    if Instant::now() > deadline {
+        abort(Aborted::Timeout);
+    }
+}
+
+/// Recorder that stops the sort once it runs past `deadline` or records more
+/// than `max_operations`.
+pub struct Guarded<R> {
+    inner: R,
+    deadline: Instant,
+    max_operations: Option<usize>,
+    recorded: usize,
+}
+
+impl<R: Recorder> Guarded<R> {
+    pub fn new(inner: R, deadline: Instant, max_operations: Option<usize>) -> Self {
+        Guarded { inner, deadline, max_operations, recorded: 0 }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Recorder> Recorder for Guarded<R> {
+    fn record(&mut self, operation: Operation) {
+        self.recorded += 1;
+        if self.max_operations.is_some_and(|max| self.recorded > max) {
+            abort(Aborted::HistoryTooLarge);
+        }
+        if self.recorded.is_multiple_of(DEADLINE_CHECK_INTERVAL) {
+            check_deadline(self.deadline);
+        }
+        self.inner.record(operation);
+    }
+}
+
+/// Starts `job` on a blocking worker right away and resolves to its result,
+/// or to the matching error if it was aborted or panicked.
+pub fn run_limited<T, F>(job: F) -> impl Future<Output = Result<T, ApiError>>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let worker = spawn_blocking(job);
+    async move {
+        worker.await.map_err(|err| {
+            match err.try_into_panic().map(Box::<dyn Any + Send>::downcast::<Aborted>) {
+                Ok(Ok(aborted)) => (*aborted).into(),
+                _ => ApiError::Internal("Sorting failed.".into()),
+            }
+        })
+    }
+}
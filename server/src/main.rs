@@ -1,7 +1,15 @@
 #![warn(clippy::all, clippy::pedantic)]
 #[macro_use] extern crate rocket;
 
+mod animation;
+mod benchmark;
+mod error;
+mod inputs;
+mod limits;
+mod maze;
+mod pathfinding;
 mod routes;
+mod session;
 mod sorting;
 mod cors;
 
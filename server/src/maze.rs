@@ -0,0 +1,450 @@
+use std::cmp::Ordering;
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::SliceRandom};
+use rocket::request::FromParam;
+use rocket::serde::{Deserialize, Serialize, json::{Json, json}};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+use algoviz_core::pathfinding::Cell;
+
+use crate::error::{ApiError, ApiResult};
+use crate::pathfinding::MAX_GRID_CELLS;
+
+#[derive(Serialize, Deserialize, EnumIter, Debug, Clone, Copy, PartialEq)]
+#[serde(crate = "rocket::serde", rename_all = "snake_case")]
+pub enum MazeAlgorithm {
+    RecursiveBacktracker,
+    Prim,
+    Kruskal,
+    RecursiveDivision,
+    Wilson,
+    Eller,
+}
+
+impl<'r> FromParam<'r> for MazeAlgorithm {
+    type Error = &'static str;
+
+    fn from_param(param: &'r str) -> Result<Self, Self::Error> {
+        match param {
+            "recursive_backtracker" => Ok(MazeAlgorithm::RecursiveBacktracker),
+            "prim" => Ok(MazeAlgorithm::Prim),
+            "kruskal" => Ok(MazeAlgorithm::Kruskal),
+            "recursive_division" => Ok(MazeAlgorithm::RecursiveDivision),
+            "wilson" => Ok(MazeAlgorithm::Wilson),
+            "eller" => Ok(MazeAlgorithm::Eller),
+            _ => Err("Invalid maze algorithm."),
+        }
+    }
+}
+
+/// Perfect maze over a grid of cells, stored as the passages between
+/// horizontally and vertically adjacent cells.
+pub struct Maze {
+    rows: usize,
+    cols: usize,
+    // passage from (row, col) to (row, col + 1)
+    right: Vec<bool>,
+    // passage from (row, col) to (row + 1, col)
+    down: Vec<bool>,
+}
+
+impl Maze {
+    fn new(rows: usize, cols: usize, open: bool) -> Self {
+        Maze { rows, cols, right: vec![open; rows * cols], down: vec![open; rows * cols] }
+    }
+
+    fn index(&self, (row, col): Cell) -> usize {
+        row * self.cols + col
+    }
+
+    fn cells(&self) -> impl Iterator<Item = Cell> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |row| (0..cols).map(move |col| (row, col)))
+    }
+
+    fn neighbours(&self, (row, col): Cell) -> Vec<Cell> {
+        let mut neighbours = Vec::with_capacity(4);
+        if col + 1 < self.cols {
+            neighbours.push((row, col + 1));
+        }
+        if row + 1 < self.rows {
+            neighbours.push((row + 1, col));
+        }
+        if col > 0 {
+            neighbours.push((row, col - 1));
+        }
+        if row > 0 {
+            neighbours.push((row - 1, col));
+        }
+        neighbours
+    }
+
+    /// Which passage list holds the edge between two adjacent cells, and where.
+    fn edge(&self, a: Cell, b: Cell) -> (bool, usize) {
+        let (first, second) = if a <= b { (a, b) } else { (b, a) };
+        (first.0 == second.0, self.index(first))
+    }
+
+    fn set_passage(&mut self, a: Cell, b: Cell, open: bool) {
+        match self.edge(a, b) {
+            (true, index) => self.right[index] = open,
+            (false, index) => self.down[index] = open,
+        }
+    }
+
+    fn connect(&mut self, a: Cell, b: Cell) {
+        self.set_passage(a, b, true);
+    }
+
+    fn disconnect(&mut self, a: Cell, b: Cell) {
+        self.set_passage(a, b, false);
+    }
+
+    fn is_connected(&self, a: Cell, b: Cell) -> bool {
+        match self.edge(a, b) {
+            (true, index) => self.right[index],
+            (false, index) => self.down[index],
+        }
+    }
+
+    fn degree(&self, cell: Cell) -> usize {
+        self.neighbours(cell).into_iter().filter(|&next| self.is_connected(cell, next)).count()
+    }
+
+    /// Walls of the maze drawn onto a `rows` x `cols` grid: cell `(r, c)` sits
+    /// at `(2r, 2c)` and the squares between cells are open only where a
+    /// passage joins them.
+    pub fn walls(&self, rows: usize, cols: usize) -> Vec<Cell> {
+        let mut walls = Vec::new();
+        for row in 0..rows {
+            for col in 0..cols {
+                let open = match (row % 2, col % 2) {
+                    (0, 0) => true,
+                    (0, 1) => col / 2 + 1 < self.cols && self.is_connected((row / 2, col / 2), (row / 2, col / 2 + 1)),
+                    (1, 0) => row / 2 + 1 < self.rows && self.is_connected((row / 2, col / 2), (row / 2 + 1, col / 2)),
+                    _ => false,
+                };
+                if !open {
+                    walls.push((row, col));
+                }
+            }
+        }
+        walls
+    }
+}
+
+/// Carves a maze of `rows` x `cols` cells, then removes a `braid` fraction of
+/// its dead ends by opening a wall next to them.
+pub fn generate<G: Rng>(algorithm: MazeAlgorithm, rows: usize, cols: usize, braid: f64, rng: &mut G) -> Maze {
+    let mut maze = match algorithm {
+        MazeAlgorithm::RecursiveBacktracker => recursive_backtracker(rows, cols, rng),
+        MazeAlgorithm::Prim => prim(rows, cols, rng),
+        MazeAlgorithm::Kruskal => kruskal(rows, cols, rng),
+        MazeAlgorithm::RecursiveDivision => recursive_division(rows, cols, rng),
+        MazeAlgorithm::Wilson => wilson(rows, cols, rng),
+        MazeAlgorithm::Eller => eller(rows, cols, rng),
+    };
+    braid_dead_ends(&mut maze, braid, rng);
+    maze
+}
+
+fn random_cell<G: Rng>(maze: &Maze, rng: &mut G) -> Cell {
+    (rng.gen_range(0..maze.rows), rng.gen_range(0..maze.cols))
+}
+
+/// Randomized depth-first search: long winding corridors, few branches.
+fn recursive_backtracker<G: Rng>(rows: usize, cols: usize, rng: &mut G) -> Maze {
+    let mut maze = Maze::new(rows, cols, false);
+    let mut visited = vec![false; rows * cols];
+    let start = random_cell(&maze, rng);
+    visited[maze.index(start)] = true;
+    let mut stack = vec![start];
+
+    while let Some(&current) = stack.last() {
+        let unvisited: Vec<Cell> = maze
+            .neighbours(current)
+            .into_iter()
+            .filter(|&next| !visited[maze.index(next)])
+            .collect();
+        match unvisited.choose(rng) {
+            Some(&next) => {
+                maze.connect(current, next);
+                visited[maze.index(next)] = true;
+                stack.push(next);
+            }
+            None => {
+                stack.pop();
+            }
+        }
+    }
+    maze
+}
+
+/// Randomized Prim: grows from one cell through random frontier edges, giving
+/// many short dead ends.
+fn prim<G: Rng>(rows: usize, cols: usize, rng: &mut G) -> Maze {
+    let mut maze = Maze::new(rows, cols, false);
+    let mut in_maze = vec![false; rows * cols];
+    let start = random_cell(&maze, rng);
+    in_maze[maze.index(start)] = true;
+    let mut frontier: Vec<(Cell, Cell)> = maze.neighbours(start).into_iter().map(|next| (start, next)).collect();
+
+    while !frontier.is_empty() {
+        let (from, to) = frontier.swap_remove(rng.gen_range(0..frontier.len()));
+        if in_maze[maze.index(to)] {
+            continue;
+        }
+        maze.connect(from, to);
+        in_maze[maze.index(to)] = true;
+        for next in maze.neighbours(to) {
+            if !in_maze[maze.index(next)] {
+                frontier.push((to, next));
+            }
+        }
+    }
+    maze
+}
+
+fn find(parents: &mut [usize], mut set: usize) -> usize {
+    while parents[set] != set {
+        parents[set] = parents[parents[set]];
+        set = parents[set];
+    }
+    set
+}
+
+/// Randomized Kruskal: joins random edges between unconnected regions.
+fn kruskal<G: Rng>(rows: usize, cols: usize, rng: &mut G) -> Maze {
+    let mut maze = Maze::new(rows, cols, false);
+    let mut edges: Vec<(Cell, Cell)> = maze
+        .cells()
+        .flat_map(|(row, col)| [((row, col), (row, col + 1)), ((row, col), (row + 1, col))])
+        .filter(|&(_, (row, col))| row < rows && col < cols)
+        .collect();
+    edges.shuffle(rng);
+    let mut parents: Vec<usize> = (0..rows * cols).collect();
+
+    for (a, b) in edges {
+        let (set_a, set_b) = (find(&mut parents, maze.index(a)), find(&mut parents, maze.index(b)));
+        if set_a != set_b {
+            parents[set_a] = set_b;
+            maze.connect(a, b);
+        }
+    }
+    maze
+}
+
+/// Starts from an open field and splits it with walls that each keep a single
+/// gap, giving long straight walls and rectangular rooms.
+fn recursive_division<G: Rng>(rows: usize, cols: usize, rng: &mut G) -> Maze {
+    let mut maze = Maze::new(rows, cols, true);
+    // chambers as (top, left, height, width) in cells
+    let mut chambers = vec![(0, 0, rows, cols)];
+
+    while let Some((top, left, height, width)) = chambers.pop() {
+        if height < 2 && width < 2 {
+            continue;
+        }
+        let horizontal = match height.cmp(&width) {
+            Ordering::Greater => true,
+            Ordering::Less => false,
+            Ordering::Equal => rng.gen(),
+        };
+        if horizontal {
+            // wall below row `top + split`, open at one column
+            let split = rng.gen_range(0..height - 1);
+            let gap = left + rng.gen_range(0..width);
+            for col in (left..left + width).filter(|&col| col != gap) {
+                maze.disconnect((top + split, col), (top + split + 1, col));
+            }
+            chambers.push((top, left, split + 1, width));
+            chambers.push((top + split + 1, left, height - split - 1, width));
+        } else {
+            let split = rng.gen_range(0..width - 1);
+            let gap = top + rng.gen_range(0..height);
+            for row in (top..top + height).filter(|&row| row != gap) {
+                maze.disconnect((row, left + split), (row, left + split + 1));
+            }
+            chambers.push((top, left, height, split + 1));
+            chambers.push((top, left + split + 1, height, width - split - 1));
+        }
+    }
+    maze
+}
+
+/// Wilson's algorithm: loop-erased random walks, which sample uniformly from
+/// all possible mazes.
+fn wilson<G: Rng>(rows: usize, cols: usize, rng: &mut G) -> Maze {
+    let mut maze = Maze::new(rows, cols, false);
+    let mut in_maze = vec![false; rows * cols];
+    let first = random_cell(&maze, rng);
+    in_maze[maze.index(first)] = true;
+    // direction the latest walk left each cell by; revisits overwrite it, which erases loops
+    let mut exit: Vec<Option<Cell>> = vec![None; rows * cols];
+    let cells: Vec<Cell> = maze.cells().collect();
+
+    for start in cells {
+        let mut current = start;
+        while !in_maze[maze.index(current)] {
+            let next = *maze.neighbours(current).choose(rng).expect("a maze with several cells has neighbours");
+            exit[maze.index(current)] = Some(next);
+            current = next;
+        }
+        current = start;
+        while !in_maze[maze.index(current)] {
+            in_maze[maze.index(current)] = true;
+            let next = exit[maze.index(current)].expect("every cell on the walk has an exit");
+            maze.connect(current, next);
+            current = next;
+        }
+    }
+    maze
+}
+
+/// Eller's algorithm: builds one row at a time while tracking which cells of
+/// the current row are already connected.
+fn eller<G: Rng>(rows: usize, cols: usize, rng: &mut G) -> Maze {
+    let mut maze = Maze::new(rows, cols, false);
+    let mut sets: Vec<Option<usize>> = vec![None; cols];
+    let mut next_set = 0;
+
+    for row in 0..rows {
+        for set in &mut sets {
+            if set.is_none() {
+                *set = Some(next_set);
+                next_set += 1;
+            }
+        }
+        let mut labels: Vec<usize> = sets.iter().flatten().copied().collect();
+        let last_row = row + 1 == rows;
+
+        for col in 0..cols.saturating_sub(1) {
+            if labels[col] != labels[col + 1] && (last_row || rng.gen()) {
+                maze.connect((row, col), (row, col + 1));
+                let (merged, kept) = (labels[col + 1], labels[col]);
+                for label in &mut labels {
+                    if *label == merged {
+                        *label = kept;
+                    }
+                }
+            }
+        }
+        if last_row {
+            break;
+        }
+
+        // every set carries on downwards through at least one cell
+        let mut next_row = vec![None; cols];
+        let mut order: Vec<usize> = (0..cols).collect();
+        order.shuffle(rng);
+        let mut extended = Vec::new();
+        for col in order {
+            let first_of_set = !extended.contains(&labels[col]);
+            if first_of_set || rng.gen_bool(0.3) {
+                maze.connect((row, col), (row + 1, col));
+                next_row[col] = Some(labels[col]);
+                extended.push(labels[col]);
+            }
+        }
+        sets = next_row;
+    }
+    maze
+}
+
+/// Opens one extra wall beside each of a `braid` fraction of the dead ends,
+/// preferring walls that lead to another dead end.
+fn braid_dead_ends<G: Rng>(maze: &mut Maze, braid: f64, rng: &mut G) {
+    let mut dead_ends: Vec<Cell> = maze.cells().collect();
+    dead_ends.retain(|&cell| maze.degree(cell) == 1);
+    dead_ends.shuffle(rng);
+
+    for cell in dead_ends {
+        if maze.degree(cell) != 1 || !rng.gen_bool(braid) {
+            continue;
+        }
+        let mut closed: Vec<Cell> = maze.neighbours(cell).into_iter().filter(|&next| !maze.is_connected(cell, next)).collect();
+        closed.shuffle(rng);
+        closed.sort_by_key(|&next| maze.degree(next) != 1);
+        if let Some(&next) = closed.first() {
+            maze.connect(cell, next);
+        }
+    }
+}
+
+/// Generates a maze on a `rows` x `cols` wall grid, in the same shape `/path`
+/// accepts. Without a `seed` a random one is picked and returned.
+#[get("/<algorithm>?<rows>&<cols>&<seed>&<braid>")]
+pub fn generate_maze(algorithm: Result<MazeAlgorithm, &'static str>, rows: usize, cols: usize, seed: Option<u64>, braid: Option<f64>) -> ApiResult {
+    let algorithm = algorithm.map_err(|reason| ApiError::NotFound(reason.into()))?;
+    let braid = braid.unwrap_or(0.0);
+    if rows == 0 || cols == 0 {
+        return Err(ApiError::UnprocessableEntity("Grid must have at least one row and one column.".into()));
+    }
+    if rows.saturating_mul(cols) > MAX_GRID_CELLS {
+        return Err(ApiError::PayloadTooLarge("Grid is too large.".into()));
+    }
+    if !(0.0..=1.0).contains(&braid) {
+        return Err(ApiError::UnprocessableEntity("Braid must be between 0 and 1.".into()));
+    }
+
+    let seed = seed.unwrap_or_else(|| rand::thread_rng().gen());
+    let maze = generate(algorithm, rows.div_ceil(2), cols.div_ceil(2), braid, &mut StdRng::seed_from_u64(seed));
+    Ok(Json(json!({
+        "status": "success",
+        "algorithm": algorithm,
+        "seed": seed,
+        "braid": braid,
+        "rows": rows,
+        "cols": cols,
+        "walls": maze.walls(rows, cols),
+    })))
+}
+
+#[get("/algorithms")]
+pub fn get_maze_algorithms() -> Json<Vec<MazeAlgorithm>> {
+    Json(MazeAlgorithm::iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// How many cells can be reached from the top left one.
+    fn reachable(maze: &Maze) -> usize {
+        let mut seen = vec![false; maze.rows * maze.cols];
+        seen[0] = true;
+        let mut stack = vec![(0, 0)];
+        let mut count = 0;
+        while let Some(cell) = stack.pop() {
+            count += 1;
+            for next in maze.neighbours(cell) {
+                if maze.is_connected(cell, next) && !seen[maze.index(next)] {
+                    seen[maze.index(next)] = true;
+                    stack.push(next);
+                }
+            }
+        }
+        count
+    }
+
+    fn passages(maze: &Maze) -> usize {
+        maze.cells().map(|cell| maze.degree(cell)).sum::<usize>() / 2
+    }
+
+    #[test]
+    fn mazes_are_connected() {
+        for algorithm in MazeAlgorithm::iter() {
+            for (rows, cols) in [(1, 1), (1, 12), (12, 1), (2, 2), (15, 20), (31, 17)] {
+                for seed in 0..5 {
+                    let cells = rows * cols;
+                    let maze = generate(algorithm, rows, cols, 0.0, &mut StdRng::seed_from_u64(seed));
+                    assert_eq!(reachable(&maze), cells, "{algorithm:?} on {rows}x{cols}, seed {seed}");
+                    // a perfect maze has exactly one route between any two cells
+                    assert_eq!(passages(&maze), cells - 1, "{algorithm:?} on {rows}x{cols}, seed {seed}");
+
+                    let braided = generate(algorithm, rows, cols, 0.5, &mut StdRng::seed_from_u64(seed));
+                    assert_eq!(reachable(&braided), cells, "braided {algorithm:?} on {rows}x{cols}, seed {seed}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use rocket::http::ContentType;
+use rocket::serde::json::{Json, json};
+use algoviz_core::pathfinding::{search, Cell, Grid, GridSpec, PathAlgorithm};
+use algoviz_core::snapshot::{grid_svg, path_steps};
+use strum::IntoEnumIterator;
+
+use crate::error::{ApiError, ApiResult};
+
+pub const MAX_GRID_CELLS: usize = 250_000;
+/// Every cell becomes an SVG element, so snapshots take smaller grids.
+const MAX_SNAPSHOT_CELLS: usize = 10_000;
+
+/// Reads the algorithm and the posted grid, rejecting grids over `max_cells`.
+fn read_request(grid_spec: Json<GridSpec>, algorithm: &str, max_cells: usize) -> Result<(PathAlgorithm, Grid, Cell, Cell), ApiError> {
+    let algorithm: PathAlgorithm = algorithm.parse().map_err(|reason: &'static str| ApiError::NotFound(reason.into()))?;
+    let grid_spec = grid_spec.into_inner();
+    if grid_spec.rows.saturating_mul(grid_spec.cols) > max_cells {
+        return Err(ApiError::PayloadTooLarge("Grid is too large.".into()));
+    }
+    let (grid, start, end) = grid_spec.into_grid().map_err(|reason| ApiError::UnprocessableEntity(reason.into()))?;
+    Ok((algorithm, grid, start, end))
+}
+
+/// Finds a path through the posted grid. Cells are `[row, col]` pairs.
+#[post("/<algorithm>", format = "json", data = "<grid_spec>")]
+pub fn find_path(grid_spec: Json<GridSpec>, algorithm: &str) -> ApiResult {
+    let (algorithm, grid, start, end) = read_request(grid_spec, algorithm, MAX_GRID_CELLS)?;
+    let result = search(algorithm, &grid, start, end);
+    Ok(Json(json!({
+        "status": "success",
+        "algorithm": algorithm,
+        "visited": result.visited,
+        "path": result.path,
+        "cost": result.cost,
+        "nodes_expanded": result.nodes_expanded,
+    })))
+}
+
+/// The posted grid as an SVG after `step` steps of the search: one per
+/// expanded cell, then one per cell of the path. Without `step` the finished
+/// search is drawn.
+#[post("/<algorithm>/snapshot?<step>", format = "json", data = "<grid_spec>")]
+pub fn snapshot_path(grid_spec: Json<GridSpec>, algorithm: &str, step: Option<usize>) -> Result<(ContentType, String), ApiError> {
+    let (algorithm, grid, start, end) = read_request(grid_spec, algorithm, MAX_SNAPSHOT_CELLS)?;
+    let result = search(algorithm, &grid, start, end);
+    let last = path_steps(&result);
+    let step = step.unwrap_or(last);
+    if step > last {
+        return Err(ApiError::UnprocessableEntity(format!("Step {step} is past the last step, {last}.").into()));
+    }
+    Ok((ContentType::SVG, grid_svg(&grid, start, end, &result, step)))
+}
+
+#[get("/algorithms")]
+pub fn get_path_algorithms() -> Json<Vec<PathAlgorithm>> {
+    Json(PathAlgorithm::iter().collect())
+}
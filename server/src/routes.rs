@@ -1,34 +1,40 @@
-use rocket::{request::FromParam, serde::{Deserialize, Serialize}};
-use rocket::serde::json::{Json, Value, json};
+use rocket::serde::{Deserialize, Serialize};
+use rocket::serde::json::{Json, json};
+use rocket::response::stream::{Event, EventStream};
+use rocket::tokio::sync::mpsc;
+use rocket::State;
+use algoviz_core::catalog::AlgorithmInfo;
+use algoviz_core::sorting::AlgorithmType;
+use algoviz_core::trace::{Metrics, Operation, Recorder};
 use strum::IntoEnumIterator;
-use strum_macros::EnumIter;
 
-use crate::sorting::{selection_sort, bubble_sort, insertion_sort, merge_sort, quick_sort, heap_sort};
+use crate::animation::{animate_sort, snapshot_sort};
+use crate::benchmark::{benchmark, time_sort};
+use crate::error::{self, ApiError, ApiResult};
+use crate::inputs::generate_input;
+use crate::limits::{run_limited, Guarded, SortLimits};
+use crate::maze::{generate_maze, get_maze_algorithms};
+use crate::pathfinding::{find_path, get_path_algorithms, snapshot_path};
+use crate::session::session;
+use crate::sorting::sort;
 
-#[derive(Serialize, Deserialize, EnumIter, Debug)]
-#[serde(crate = "rocket::serde")]
-enum AlgorithmType {
-    Selection,
-    Bubble,
-    Insertion,
-    Merge,
-    Quick,
-    Heap,
+/// How many operations a streaming sort may run ahead of the client.
+const STREAM_BUFFER: usize = 256;
+const DEFAULT_TIMING_RUNS: u32 = 10;
+const MAX_TIMING_RUNS: u32 = 1000;
+
+/// Reads the algorithm named in the path; unknown names are a 404.
+pub fn parse_algorithm(slug: &str) -> Result<AlgorithmType, ApiError> {
+    slug.parse().map_err(|reason: &'static str| ApiError::NotFound(reason.into()))
 }
 
-impl<'r> FromParam<'r> for AlgorithmType {
-    type Error = &'r str;
+/// Forwards operations to an async consumer. Must be driven from a blocking
+/// thread; once the receiver is gone further operations are dropped.
+struct Forward(mpsc::Sender<Operation>);
 
-    fn from_param(param: &'r str) -> Result<Self, Self::Error> {
-        match param {
-            "selection" => Ok(AlgorithmType::Selection),
-            "bubble" => Ok(AlgorithmType::Bubble),
-            "insertion" => Ok(AlgorithmType::Insertion),
-            "merge" => Ok(AlgorithmType::Merge),
-            "quick" => Ok(AlgorithmType::Quick),
-            "heap" => Ok(AlgorithmType::Heap),
-            _ => Err("Invalid algorithm type."),
-        }
+impl Recorder for Forward {
+    fn record(&mut self, operation: Operation) {
+        let _ = self.0.blocking_send(operation);
     }
 }
 
@@ -40,58 +46,118 @@ struct SortRequest {
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(crate = "rocket::serde")]
-pub struct SortResult {
+pub struct SortResult<R = Vec<Operation>> {
     pub result: Vec<i64>,
-    pub array_accesses: i64,
+    pub operations: R,
+    pub metrics: Metrics,
     pub duration: std::time::Duration,
 }
 
+/// Serialized straight from the typed result: building a `Value` tree for a
+/// long trace would take many times the memory of the trace itself.
+#[derive(Serialize, Debug)]
+#[serde(crate = "rocket::serde")]
+struct SortResponse {
+    status: &'static str,
+    #[serde(flatten)]
+    sort: SortResult,
+    results_length: usize,
+}
+
 #[post("/<algorithm_type>", format = "json", data = "<sort_request>")]
-fn sort_numbers(sort_request: Json<SortRequest>, algorithm_type: Result<AlgorithmType, &str>) -> Json<Value> {
-    match algorithm_type {
-    Ok(algorithm) => {
-        let sort_request_length = sort_request.numbers.len();
-        let result = match algorithm { 
-            AlgorithmType::Selection => selection_sort(sort_request.numbers.clone()),
-            AlgorithmType::Bubble => bubble_sort(sort_request.numbers.clone()),
-            AlgorithmType::Insertion => insertion_sort(sort_request.numbers.clone()),
-            AlgorithmType::Merge => merge_sort(sort_request.numbers.clone()),
-            AlgorithmType::Quick => quick_sort(sort_request.numbers.clone()),
-            AlgorithmType::Heap => heap_sort(sort_request.numbers.clone()),
-        };
-        Json(json!({
-            "status": "success",
-            "result": result.result,
-            "array_accesses": result.array_accesses,
-            "duration": result.duration,
-            "results_length": sort_request_length,
-        }))
-    },
-    Err(err) => {
-        Json(json!({
-            "status": "error",
-            "reason": err
-        }))
-    }
-    }
+async fn sort_numbers(sort_request: Json<SortRequest>, algorithm_type: &str, limits: &State<SortLimits>) -> ApiResult<SortResponse> {
+    let algorithm = parse_algorithm(algorithm_type)?;
+    let numbers = sort_request.into_inner().numbers;
+    limits.check_numbers(algorithm, &numbers)?;
+    let sort_request_length = numbers.len();
+    let recorder = Guarded::new(Vec::new(), limits.deadline(), Some(limits.max_operations(algorithm)));
+    let result = run_limited(move || sort(algorithm, numbers, recorder)).await?;
+    Ok(Json(SortResponse {
+        status: "success",
+        sort: SortResult {
+            result: result.result,
+            operations: result.operations.into_inner(),
+            metrics: result.metrics,
+            duration: result.duration,
+        },
+        results_length: sort_request_length,
+    }))
+}
+
+/// Operations are sent as they happen rather than kept, so only the length
+/// and time limits apply.
+#[get("/<algorithm_type>/stream?<numbers>")]
+fn stream_sort(algorithm_type: &str, numbers: Vec<i64>, limits: &State<SortLimits>) -> Result<EventStream![], ApiError> {
+    let algorithm = parse_algorithm(algorithm_type)?;
+    limits.check_numbers(algorithm, &numbers)?;
+    let results_length = numbers.len();
+    let (sender, mut receiver) = mpsc::channel(STREAM_BUFFER);
+    // the sender lives on the worker, so the channel closes as soon as the sort returns
+    let mut recorder = Guarded::new(Forward(sender), limits.deadline(), None);
+    let worker = run_limited(move || {
+        let result = sort(algorithm, numbers, &mut recorder);
+        (result.result, result.metrics, result.duration)
+    });
+
+    Ok(EventStream! {
+        while let Some(operation) = receiver.recv().await {
+            yield Event::json(&operation).event("operation");
+        }
+        match worker.await {
+            Ok((result, metrics, duration)) => yield Event::json(&json!({
+                "status": "success",
+                "result": result,
+                "metrics": metrics,
+                "duration": duration,
+                "results_length": results_length,
+            })).event("summary"),
+            // headers are already sent, so the failure can only be reported in the stream
+            Err(err) => yield Event::json(&err.body()).event("summary"),
+        }
+    })
+}
+
+#[post("/<algorithm_type>/timing?<runs>", format = "json", data = "<sort_request>")]
+async fn time_sort_numbers(sort_request: Json<SortRequest>, algorithm_type: &str, runs: Option<u32>, limits: &State<SortLimits>) -> ApiResult {
+    let algorithm = parse_algorithm(algorithm_type)?;
+    let runs = runs.unwrap_or(DEFAULT_TIMING_RUNS).clamp(1, MAX_TIMING_RUNS);
+    let numbers = sort_request.into_inner().numbers;
+    limits.check_numbers(algorithm, &numbers)?;
+    let results_length = numbers.len();
+    let deadline = limits.deadline();
+    let timing = run_limited(move || time_sort(algorithm, &numbers, runs, deadline)).await?;
+    Ok(Json(json!({
+        "status": "success",
+        "timing": timing,
+        "results_length": results_length,
+    })))
 }
 
 #[get("/algorithms")]
-fn get_sorting_algorithms() -> Json<Vec<String>> {
-    let algorithms: Vec<String> = AlgorithmType::iter().map(|alg| format!("{:?}", alg)).collect();
-    Json(algorithms)
+fn get_sorting_algorithms() -> Json<Vec<AlgorithmInfo>> {
+    Json(AlgorithmType::iter().map(AlgorithmType::info).collect())
 }
 
-#[catch(404)]
-fn not_found() -> Value {
-    json!({
-        "status": "error",
-        "reason": "Resource was not found."
-    })
+#[get("/algorithms/<algorithm_type>")]
+fn get_sorting_algorithm(algorithm_type: &str) -> ApiResult<AlgorithmInfo> {
+    Ok(Json(parse_algorithm(algorithm_type)?.info()))
 }
 
 pub fn stage() -> rocket::fairing::AdHoc {
-    rocket::fairing::AdHoc::on_ignite("JSON", |rocket| async {
-        rocket.mount("/sort", routes![sort_numbers, get_sorting_algorithms]).register("/sort", catchers![not_found])
+    rocket::fairing::AdHoc::try_on_ignite("JSON", |rocket| async {
+        let limits = match rocket.figment().extract_inner::<SortLimits>("sorting") {
+            Ok(limits) => limits,
+            Err(err) if err.missing() => SortLimits::default(),
+            Err(err) => {
+                error!("Invalid sorting limits: {err}");
+                return Err(rocket);
+            }
+        };
+        Ok(rocket.manage(limits)
+            .mount("/sort", routes![sort_numbers, stream_sort, time_sort_numbers, animate_sort, snapshot_sort, session, get_sorting_algorithms, get_sorting_algorithm])
+            .mount("/path", routes![find_path, snapshot_path, get_path_algorithms])
+            .mount("/maze", routes![generate_maze, get_maze_algorithms])
+            .mount("/", routes![benchmark, generate_input])
+            .register("/", error::catchers()))
     })
 }
@@ -0,0 +1,148 @@
+use rocket::futures::{SinkExt, StreamExt};
+use rocket::serde::{Deserialize, json::{Value, json}};
+use rocket::State;
+
+use algoviz_core::trace::Operation;
+
+use crate::error::ApiError;
+use crate::limits::{run_limited, Guarded, SortLimits};
+use crate::routes::parse_algorithm;
+use crate::sorting::sort;
+
+#[derive(Deserialize, Debug)]
+#[serde(crate = "rocket::serde", tag = "command", rename_all = "snake_case")]
+enum Command {
+    Start { algorithm: String, numbers: Vec<i64> },
+    Step,
+    StepN { n: usize },
+    RunToEnd,
+    Back,
+    Reset,
+}
+
+/// Replays a recorded trace one operation at a time, in either direction.
+struct Stepper {
+    values: Vec<i64>,
+    operations: Vec<Operation>,
+    position: usize,
+    // value overwritten by every `Write` applied so far, popped again by `back`
+    overwritten: Vec<i64>,
+}
+
+impl Stepper {
+    fn new(values: Vec<i64>, operations: Vec<Operation>) -> Self {
+        Stepper { values, operations, position: 0, overwritten: Vec::new() }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.position == self.operations.len()
+    }
+
+    fn step(&mut self) {
+        let Some(operation) = self.operations.get(self.position) else {
+            return;
+        };
+        match *operation {
+            Operation::Swap { i, j } => self.values.swap(i, j),
+            Operation::Write { index, value } => {
+                self.overwritten.push(self.values[index]);
+                self.values[index] = value;
+            }
+            _ => {}
+        }
+        self.position += 1;
+    }
+
+    fn step_n(&mut self, n: usize) {
+        // stepping past the end does nothing, so a huge `n` must not loop over it
+        for _ in 0..n.min(self.operations.len() - self.position) {
+            self.step();
+        }
+    }
+
+    fn run_to_end(&mut self) {
+        while !self.is_finished() {
+            self.step();
+        }
+    }
+
+    fn back(&mut self) {
+        if self.position == 0 {
+            return;
+        }
+        self.position -= 1;
+        match self.operations[self.position] {
+            Operation::Swap { i, j } => self.values.swap(i, j),
+            Operation::Write { index, .. } => {
+                if let Some(previous) = self.overwritten.pop() {
+                    self.values[index] = previous;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn reset(&mut self) {
+        while self.position > 0 {
+            self.back();
+        }
+    }
+
+    fn state(&self) -> Value {
+        json!({
+            "status": "success",
+            "array": self.values,
+            "operation": self.position.checked_sub(1).map(|last| &self.operations[last]),
+            "step": self.position,
+            "total_steps": self.operations.len(),
+            "finished": self.is_finished(),
+        })
+    }
+}
+
+async fn start(algorithm: &str, numbers: Vec<i64>, limits: &SortLimits) -> Result<Stepper, ApiError> {
+    let algorithm = parse_algorithm(algorithm)?;
+    limits.check_numbers(algorithm, &numbers)?;
+    let initial = numbers.clone();
+    let recorder = Guarded::new(Vec::new(), limits.deadline(), Some(limits.max_operations(algorithm)));
+    let result = run_limited(move || sort(algorithm, numbers, recorder)).await?;
+    Ok(Stepper::new(initial, result.operations.into_inner()))
+}
+
+async fn handle(stepper: &mut Option<Stepper>, message: &str, limits: &SortLimits) -> Value {
+    let Ok(command) = rocket::serde::json::from_str::<Command>(message) else {
+        return ApiError::BadRequest("Invalid command.".into()).body();
+    };
+    match (command, stepper) {
+        (Command::Start { algorithm, numbers }, stepper) => match start(&algorithm, numbers, limits).await {
+            Ok(started) => stepper.insert(started).state(),
+            Err(err) => err.body(),
+        },
+        (_, None) => ApiError::UnprocessableEntity("No session started.".into()).body(),
+        (Command::Step, Some(active)) => { active.step(); active.state() }
+        (Command::StepN { n }, Some(active)) => { active.step_n(n); active.state() }
+        (Command::RunToEnd, Some(active)) => { active.run_to_end(); active.state() }
+        (Command::Back, Some(active)) => { active.back(); active.state() }
+        (Command::Reset, Some(active)) => { active.reset(); active.state() }
+    }
+}
+
+/// Interactive stepping session: the client sends `start` with an algorithm and
+/// array, then drives the recorded run with `step`, `step_n`, `run_to_end`,
+/// `back` and `reset`, receiving the array and last operation after each.
+#[get("/session")]
+pub fn session(ws: ws::WebSocket, limits: &State<SortLimits>) -> ws::Channel<'static> {
+    let limits = limits.inner().clone();
+    ws.channel(move |mut stream| Box::pin(async move {
+        let mut stepper = None;
+        while let Some(message) = stream.next().await {
+            let reply = match message? {
+                ws::Message::Text(text) => handle(&mut stepper, &text, &limits).await,
+                ws::Message::Close(_) => break,
+                _ => continue,
+            };
+            stream.send(ws::Message::Text(reply.to_string())).await?;
+        }
+        Ok(())
+    }))
+}
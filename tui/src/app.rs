@@ -0,0 +1,92 @@
+use std::time::Duration;
+use algoviz_core::pathfinding::{Cell, Grid, PathAlgorithm, PathResult};
+use algoviz_core::replay::Replay;
+use algoviz_core::sorting::AlgorithmType;
+
+/// Playback speeds in steps per second.
+const SPEEDS: [u32; 9] = [1, 2, 5, 10, 20, 50, 100, 200, 500];
+
+pub enum Run {
+    Sort {
+        algorithm: AlgorithmType,
+        replay: Replay,
+    },
+    Path {
+        algorithm: PathAlgorithm,
+        grid: Grid,
+        start: Cell,
+        end: Cell,
+        result: PathResult,
+    },
+}
+
+/// A run being played back: which step is on screen and how fast it moves.
+pub struct App {
+    pub run: Run,
+    pub step: usize,
+    pub playing: bool,
+    speed: usize,
+}
+
+impl App {
+    pub fn new(run: Run) -> Self {
+        App { run, step: 0, playing: false, speed: 3 }
+    }
+
+    /// Number of steps in the whole run.
+    pub fn steps(&self) -> usize {
+        match &self.run {
+            Run::Sort { replay, .. } => replay.steps(),
+            Run::Path { result, .. } => result.visited.len() + result.path.len(),
+        }
+    }
+
+    pub fn speed(&self) -> u32 {
+        SPEEDS[self.speed]
+    }
+
+    pub fn step_interval(&self) -> Duration {
+        Duration::from_secs(1) / self.speed()
+    }
+
+    pub fn faster(&mut self) {
+        self.speed = (self.speed + 1).min(SPEEDS.len() - 1);
+    }
+
+    pub fn slower(&mut self) {
+        self.speed = self.speed.saturating_sub(1);
+    }
+
+    /// Starts or pauses playback; playing a finished run starts it over.
+    pub fn toggle(&mut self) {
+        if !self.playing && self.step == self.steps() {
+            self.seek(0);
+        }
+        self.playing = !self.playing;
+    }
+
+    pub fn restart(&mut self) {
+        self.seek(0);
+        self.playing = false;
+    }
+
+    /// Moves forward by up to `count` steps and stops playing at the end.
+    pub fn forward(&mut self, count: usize) {
+        self.seek((self.step + count).min(self.steps()));
+        if self.step == self.steps() {
+            self.playing = false;
+        }
+    }
+
+    pub fn back(&mut self) {
+        self.playing = false;
+        self.seek(self.step.saturating_sub(1));
+    }
+
+    fn seek(&mut self, step: usize) {
+        if let Run::Sort { replay, .. } = &mut self.run {
+            replay.seek(step);
+        }
+        self.step = step;
+    }
+}
@@ -0,0 +1,183 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+mod app;
+mod ui;
+
+use std::io;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+use algoviz_core::input::{parse_grid, parse_numbers};
+use algoviz_core::pathfinding::{self, GridSpec, PathAlgorithm};
+use algoviz_core::replay::Replay;
+use algoviz_core::sorting::{self, AlgorithmType};
+use clap::{Parser, Subcommand};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::DefaultTerminal;
+
+use crate::app::{App, Run};
+
+/// Plays sorting and pathfinding runs back in the terminal.
+#[derive(Parser, Debug)]
+#[command(name = "algoviz-tui", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Sort the given integers, or a random list without `--input`/`--file`.
+    Sort {
+        /// Sorting algorithm, e.g. `quick`.
+        algorithm: AlgorithmType,
+        /// Integers separated by commas or whitespace.
+        #[arg(long, conflicts_with = "file")]
+        input: Option<String>,
+        #[arg(long)]
+        file: Option<PathBuf>,
+        /// Length of the random list.
+        #[arg(long, default_value_t = 32)]
+        size: usize,
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Search a grid given as JSON or a text map, or a random one without
+    /// `--input`/`--file`.
+    ///
+    /// The formats are the ones `algoviz path` reads.
+    Path {
+        /// Pathfinding algorithm, e.g. `astar`.
+        algorithm: PathAlgorithm,
+        #[arg(long, conflicts_with = "file")]
+        input: Option<String>,
+        #[arg(long)]
+        file: Option<PathBuf>,
+        #[arg(long, default_value_t = 15)]
+        rows: usize,
+        #[arg(long, default_value_t = 30)]
+        cols: usize,
+        /// Share of random cells that become walls.
+        #[arg(long, default_value_t = 0.25)]
+        walls: f64,
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+}
+
+fn rng(seed: Option<u64>) -> StdRng {
+    seed.map_or_else(StdRng::from_entropy, StdRng::seed_from_u64)
+}
+
+fn read(input: Option<String>, file: Option<PathBuf>) -> Result<Option<String>, String> {
+    match (input, file) {
+        (Some(input), _) => Ok(Some(input)),
+        (None, Some(file)) => std::fs::read_to_string(&file).map(Some).map_err(|err| format!("cannot read {}: {err}", file.display())),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Records the whole run up front so playback can move in both directions.
+fn prepare(command: Command) -> Result<Run, String> {
+    match command {
+        Command::Sort { algorithm, input, file, size, seed } => {
+            let numbers = if let Some(input) = read(input, file)? {
+                parse_numbers(&input)?
+            } else {
+                let mut rng = rng(seed);
+                (0..size.max(1)).map(|_| rng.gen_range(1..=100)).collect()
+            };
+            let operations = sorting::sort(algorithm, numbers.clone(), Vec::new()).operations;
+            Ok(Run::Sort { algorithm, replay: Replay::new(numbers, operations) })
+        }
+        Command::Path { algorithm, input, file, rows, cols, walls, seed } => {
+            let spec = match read(input, file)? {
+                Some(input) => parse_grid(&input)?,
+                None => random_grid(rows, cols, walls, seed),
+            };
+            let (grid, start, end) = spec.into_grid()?;
+            let result = pathfinding::search(algorithm, &grid, start, end);
+            Ok(Run::Path { algorithm, grid, start, end, result })
+        }
+    }
+}
+
+/// Scattered walls between the top-left start and the bottom-right end.
+fn random_grid(rows: usize, cols: usize, density: f64, seed: Option<u64>) -> GridSpec {
+    let (rows, cols) = (rows.max(1), cols.max(2));
+    let (start, end) = ((0, 0), (rows - 1, cols - 1));
+    let mut rng = rng(seed);
+    let walls = (0..rows)
+        .flat_map(|row| (0..cols).map(move |col| (row, col)))
+        .filter(|&cell| cell != start && cell != end && rng.gen_bool(density.clamp(0.0, 1.0)))
+        .collect();
+    GridSpec { rows, cols, walls, start, end, weights: None }
+}
+
+fn run(terminal: &mut DefaultTerminal, mut app: App) -> io::Result<()> {
+    let mut last_step = Instant::now();
+    loop {
+        terminal.draw(|screen| ui::draw(screen, &app))?;
+
+        let timeout = if app.playing { app.step_interval().saturating_sub(last_step.elapsed()) } else { Duration::from_secs(1) };
+        if event::poll(timeout)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Char(' ') => {
+                        app.toggle();
+                        last_step = Instant::now();
+                    }
+                    KeyCode::Right | KeyCode::Char('l') => {
+                        app.playing = false;
+                        app.forward(1);
+                    }
+                    KeyCode::Left | KeyCode::Char('h') => app.back(),
+                    KeyCode::Char('+' | '=') => app.faster(),
+                    KeyCode::Char('-' | '_') => app.slower(),
+                    KeyCode::Char('r') => app.restart(),
+                    _ => {}
+                }
+            }
+        }
+
+        if app.playing {
+            // Catch up in whole steps, so fast speeds still keep time when a
+            // frame takes longer to draw than one step lasts.
+            let interval = app.step_interval();
+            let due = last_step.elapsed().as_nanos() / interval.as_nanos().max(1);
+            if due > 0 {
+                app.forward(usize::try_from(due).unwrap_or(usize::MAX));
+                last_step += interval * u32::try_from(due).unwrap_or(u32::MAX);
+            }
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let run_to_play = match prepare(Cli::parse().command) {
+        Ok(run_to_play) => run_to_play,
+        Err(reason) => {
+            eprintln!("error: {reason}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = ratatui::try_init().and_then(|mut terminal| {
+        let result = run(&mut terminal, App::new(run_to_play));
+        ratatui::restore();
+        result
+    });
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
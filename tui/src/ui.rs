@@ -0,0 +1,130 @@
+use algoviz_core::pathfinding::{Cell, Grid, PathResult};
+use algoviz_core::replay::Frame;
+use algoviz_core::sorting::AlgorithmType;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Bar, BarChart, BarGroup, Block, Paragraph};
+use ratatui::Frame as Screen;
+
+use crate::app::{App, Run};
+
+const GREY: Color = Color::Gray;
+const GREEN: Color = Color::Green;
+const ORANGE: Color = Color::Indexed(208);
+const RED: Color = Color::Red;
+
+const HELP: &str = "space play/pause  ←/→ step  +/- speed  r restart  q quit";
+
+pub fn draw(screen: &mut Screen, app: &App) {
+    let [main, status, help] = Layout::vertical([Constraint::Min(3), Constraint::Length(1), Constraint::Length(1)]).areas(screen.area());
+    match &app.run {
+        Run::Sort { algorithm, replay } => draw_bars(screen, main, *algorithm, replay.frame()),
+        Run::Path { grid, start, end, result, .. } => draw_grid(screen, main, grid, *start, *end, result, app.step),
+    }
+    screen.render_widget(Paragraph::new(status_line(app)), status);
+    screen.render_widget(Paragraph::new(HELP).dark_gray(), help);
+}
+
+fn status_line(app: &App) -> Line<'static> {
+    let name = match &app.run {
+        Run::Sort { algorithm, .. } => algorithm.info().name.to_string(),
+        Run::Path { algorithm, .. } => algorithm.to_string(),
+    };
+    let state = if app.playing { "playing" } else { "paused" };
+    let mut spans = vec![
+        Span::from(name).bold(),
+        Span::from(format!("  step {}/{}  {state}  {} steps/s", app.step, app.steps(), app.speed())),
+    ];
+    if let Run::Path { result, .. } = &app.run {
+        if app.step == app.steps() {
+            let cost = result.cost.map_or_else(|| "unreachable".to_string(), |cost| format!("cost {cost}"));
+            spans.push(Span::from(format!("  {cost}, {} nodes expanded", result.nodes_expanded)));
+        }
+    }
+    Line::from(spans)
+}
+
+/// One bar per value, shifted up so negative values still get a bar.
+fn draw_bars(screen: &mut Screen, area: Rect, algorithm: AlgorithmType, frame: &Frame) {
+    let block = Block::bordered().title(" bars ");
+    let width = usize::from(block.inner(area).width);
+    let count = frame.values.len().max(1);
+    let gap = u16::from(count * 2 <= width);
+    let bar_width = u16::try_from((width.saturating_sub((count - 1) * usize::from(gap)) / count).max(1)).unwrap_or(1);
+
+    // in i128, so the heights of values near the ends of i64 cannot overflow
+    let floor = i128::from(frame.values.iter().min().copied().unwrap_or(0).min(1)) - 1;
+    let pivot = frame.pivot(algorithm);
+    let bars: Vec<Bar> = frame
+        .values
+        .iter()
+        .enumerate()
+        .map(|(index, &value)| {
+            let color = if pivot == Some(index) {
+                RED
+            } else if frame.is_comparing(index) {
+                GREEN
+            } else if frame.is_sorted(index) {
+                ORANGE
+            } else {
+                GREY
+            };
+            Bar::default()
+                .value(u64::try_from(i128::from(value) - floor).unwrap_or(u64::MAX))
+                .text_value(value.to_string())
+                .style(Style::new().fg(color))
+                .value_style(Style::new().fg(Color::Black).bg(color))
+        })
+        .collect();
+
+    let chart = BarChart::default().block(block).bar_width(bar_width).bar_gap(gap).data(BarGroup::default().bars(&bars));
+    screen.render_widget(chart, area);
+}
+
+/// The grid two columns per cell: expanded cells in blue, the path in yellow
+/// and the last expanded cell in magenta while the search is still running.
+fn draw_grid(screen: &mut Screen, area: Rect, grid: &Grid, start: Cell, end: Cell, result: &PathResult, step: usize) {
+    let expanded = step.min(result.visited.len());
+    let on_path = step - expanded;
+    let mut colors: Vec<Vec<Option<Color>>> = vec![vec![None; grid.cols()]; grid.rows()];
+    for &(row, col) in &result.visited[..expanded] {
+        colors[row][col] = Some(Color::Blue);
+    }
+    if on_path == 0 {
+        if let Some(&(row, col)) = result.visited[..expanded].last() {
+            colors[row][col] = Some(Color::Magenta);
+        }
+    }
+    for &(row, col) in &result.path[..on_path] {
+        colors[row][col] = Some(Color::Yellow);
+    }
+
+    let lines: Vec<Line> = (0..grid.rows())
+        .map(|row| {
+            let cells = (0..grid.cols()).map(|col| {
+                let cell = (row, col);
+                if cell == start {
+                    Span::from("S ").black().on_green()
+                } else if cell == end {
+                    Span::from("E ").black().on_red()
+                } else if grid.is_wall(cell) {
+                    Span::from("██").white()
+                } else {
+                    let weight = grid.weight(cell);
+                    let text = match weight {
+                        1 => "  ".to_string(),
+                        2..=9 => format!("{weight} "),
+                        _ => "+ ".to_string(),
+                    };
+                    match colors[row][col] {
+                        Some(color) => Span::from(text).black().bg(color),
+                        None => Span::from(if weight == 1 { "· ".to_string() } else { text }).dark_gray(),
+                    }
+                }
+            });
+            Line::from(cells.collect::<Vec<_>>())
+        })
+        .collect();
+    screen.render_widget(Paragraph::new(lines).block(Block::bordered().title(" grid ")), area);
+}